@@ -1,13 +1,37 @@
+use std::cell::RefCell;
 use std::fmt;
 use std::io::{Error, ErrorKind, Result};
+use std::rc::Rc;
 use std::str::Chars;
 
+/// A byte range within the text that was parsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
 #[derive(Clone, Debug)]
 pub struct Code<'a> {
     content: &'a str,
     cursor: usize,
+    /// The first malformed literal met, shared by every clone so that it survives backtracking.
+    malformed: Rc<RefCell<Option<String>>>,
 }
 
+/// Text that cannot be read however it is laid out, such as `16#GG` or `'a$Qb'`, as opposed to
+/// text that no layout matches.
+#[derive(Debug)]
+pub struct Malformed(pub String);
+
+impl fmt::Display for Malformed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Malformed {}
+
 impl fmt::Display for Code<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", &self.content[self.cursor..])
@@ -24,7 +48,40 @@ impl<'a> Code<'a> {
     }
 
     pub fn from(content: &'a str) -> Self {
-        Self { content, cursor: 0 }
+        Self {
+            content,
+            cursor: 0,
+            malformed: Rc::default(),
+        }
+    }
+
+    /// Keeps `error` as the reason the parse failed, unless an earlier one is already kept.
+    pub fn report_malformed(&self, error: &Error) {
+        self.malformed
+            .borrow_mut()
+            .get_or_insert_with(|| error.to_string());
+    }
+
+    /// The malformed literal that stopped the parse, if any.
+    pub fn malformed(&self) -> Option<Error> {
+        self.malformed
+            .borrow()
+            .as_ref()
+            .map(|reason| Error::new(ErrorKind::InvalidData, Malformed(reason.clone())))
+    }
+
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// The number of characters between the start of the current line and the cursor.
+    pub fn column(&self) -> usize {
+        let line_start = self.content[..self.cursor].rfind('\n').map_or(0, |i| i + 1);
+        self.content[line_start..self.cursor].chars().count()
+    }
+
+    pub fn since(&self, start: usize) -> &'a str {
+        &self.content[start..self.cursor]
     }
 
     pub fn peel(&mut self, by: usize) -> Result<()> {
@@ -51,12 +108,23 @@ impl<'a> Code<'a> {
         self.strip_between_and_trim_inner_common(start, end, true)
     }
 
+    pub fn strip_between_nestable(&mut self, start: &str, end: &str) -> Result<&'a str> {
+        self.strip_between_common(start, end, true)
+    }
+
     fn strip_between_and_trim_inner_common(
         &mut self,
         start: &str,
         end: &str,
         nestable: bool,
     ) -> Result<String> {
+        Ok(self
+            .strip_between_common(start, end, nestable)?
+            .trim()
+            .to_string())
+    }
+
+    fn strip_between_common(&mut self, start: &str, end: &str, nestable: bool) -> Result<&'a str> {
         if !self.content[self.cursor..].starts_with(start) {
             return Err(Error::new(
                 ErrorKind::InvalidData,
@@ -90,9 +158,7 @@ impl<'a> Code<'a> {
             }
         }
 
-        let inner = self.content[self.cursor + start.len()..index_end]
-            .trim()
-            .to_string();
+        let inner = &self.content[self.cursor + start.len()..index_end];
         self.cursor = index_end + end.len();
         Ok(inner)
     }
@@ -151,6 +217,28 @@ impl<'a> Code<'a> {
         }
     }
 
+    /// Like `strip_prefix_uppercase`, but only if the keyword is not the start of a longer name.
+    pub fn strip_prefix_keyword(&self, text: &str) -> Result<Self> {
+        let output = self.strip_prefix_uppercase(text)?;
+        match output.chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '_' => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Does not start with keyword {text}\n{self}"),
+            )),
+            Some(_) | None => Ok(output),
+        }
+    }
+
+    /// Like `trim_start`, but stops at line feeds.
+    pub fn trim_start_spaces(&self) -> Self {
+        let trimmed = self.content[self.cursor..].trim_start_matches([' ', '\t']);
+        Self {
+            content: self.content,
+            cursor: self.content.len() - trimmed.len(),
+            malformed: self.malformed.clone(),
+        }
+    }
+
     pub fn trim_start(&self) -> Self {
         let mut cursor = self.cursor;
         for c in self.content[self.cursor..].chars() {
@@ -163,6 +251,7 @@ impl<'a> Code<'a> {
         Self {
             content: self.content,
             cursor,
+            malformed: self.malformed.clone(),
         }
     }
 }
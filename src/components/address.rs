@@ -2,33 +2,160 @@ use std::fmt;
 use std::io::{Error, ErrorKind, Result};
 
 use crate::code::Code;
+use crate::fmt::KeywordCase;
 
-#[derive(Debug, PartialEq)]
-pub enum Address {
-    I,
-    Q,
-    M,
+use super::Spelling;
+
+/// A direct address such as `AT %IX0.1`, `AT %QW10` or `AT %M*`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Address {
+    pub area: Area,
+    pub size: Option<Size>,
+    pub location: Location,
+    /// How `AT` was written.
+    pub spelling: Spelling,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Area {
+    Input,
+    Output,
+    Memory,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Size {
+    Bit,
+    Byte,
+    Word,
+    DoubleWord,
+    LongWord,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Location {
+    /// `*`, to be assigned later in the I/O configuration.
+    Unspecified,
+    /// Hierarchical numbers, such as `0.1`.
+    Numbers(Vec<u32>),
+}
+
+impl Area {
+    fn prefix(&self) -> char {
+        match self {
+            Self::Input => 'I',
+            Self::Output => 'Q',
+            Self::Memory => 'M',
+        }
+    }
+}
+
+impl Size {
+    fn prefix(&self) -> char {
+        match self {
+            Self::Bit => 'X',
+            Self::Byte => 'B',
+            Self::Word => 'W',
+            Self::DoubleWord => 'D',
+            Self::LongWord => 'L',
+        }
+    }
+
+    pub fn bits(&self) -> u64 {
+        match self {
+            Self::Bit => 1,
+            Self::Byte => 8,
+            Self::Word => 16,
+            Self::DoubleWord => 32,
+            Self::LongWord => 64,
+        }
+    }
 }
 
 impl fmt::Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::I => write!(f, "AT %I*"),
-            Self::Q => write!(f, "AT %Q*"),
-            Self::M => write!(f, "AT %M*"),
+        self.write_cased(f, KeywordCase::default())
+    }
+}
+
+impl Address {
+    /// Writes the address with `AT` spelled as `case` asks.
+    pub fn write_cased(&self, f: &mut dyn fmt::Write, case: KeywordCase) -> fmt::Result {
+        write!(
+            f,
+            "{} %{}",
+            case.apply("AT", &self.spelling),
+            self.area.prefix()
+        )?;
+        if let Some(size) = self.size {
+            write!(f, "{}", size.prefix())?;
+        }
+        match &self.location {
+            Location::Unspecified => write!(f, "*"),
+            Location::Numbers(numbers) => {
+                for (i, number) in numbers.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{number}")?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
 impl Address {
+    /// The number of bits addressed; an address without a size prefix is a single bit.
+    pub fn bit_width(&self) -> u64 {
+        self.size.map_or(1, |size| size.bits())
+    }
+
+    /// The first bit addressed within the area, counting from bit 0 of byte 0.
+    ///
+    /// Words, double words and long words are numbered in units of their own size, so `%MW1`
+    /// starts at byte 2. Returns `None` for `*` and for deeper hierarchies.
+    pub fn bit_offset(&self) -> Option<u64> {
+        let Location::Numbers(numbers) = &self.location else {
+            return None;
+        };
+        match (self.size, numbers.as_slice()) {
+            (None | Some(Size::Bit), [byte, bit]) if *bit < 8 => {
+                Some(u64::from(*byte) * 8 + u64::from(*bit))
+            }
+            (None | Some(Size::Bit), [bit]) => Some(u64::from(*bit)),
+            (Some(size), [index]) => Some(u64::from(*index) * size.bits()),
+            _ => None,
+        }
+    }
+
+    /// The byte containing the first bit addressed.
+    pub fn byte_offset(&self) -> Option<u64> {
+        self.bit_offset().map(|bit| bit / 8)
+    }
+
+    /// Whether both addresses share at least one bit of the same area.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        match (self.bit_offset(), other.bit_offset()) {
+            (Some(start), Some(other_start)) => {
+                self.area == other.area
+                    && start < other_start + other.bit_width()
+                    && other_start < start + self.bit_width()
+            }
+            _ => false,
+        }
+    }
+
     pub fn peel(code: &mut Code) -> Result<Self> {
-        let mut code_clone = code.strip_prefix_uppercase("AT")?.trim_start();
+        let code_at = code.strip_prefix_uppercase("AT")?;
+        let spelling = Spelling::between(code, &code_at);
+        let mut code_clone = code_at.trim_start();
         code_clone = code_clone.strip_prefix('%')?;
 
-        let address = match code_clone.chars().next() {
-            Some('I') => Address::I,
-            Some('Q') => Address::Q,
-            Some('M') => Address::M,
+        let area = match code_clone.chars().next().map(|c| c.to_ascii_uppercase()) {
+            Some('I') => Area::Input,
+            Some('Q') => Area::Output,
+            Some('M') => Area::Memory,
             Some(_) | None => {
                 return Err(Error::new(
                     ErrorKind::InvalidData,
@@ -38,8 +165,59 @@ impl Address {
         };
         code_clone.peel(1)?;
 
-        *code = code_clone.strip_prefix('*')?;
-        Ok(address)
+        let size = match code_clone.chars().next().map(|c| c.to_ascii_uppercase()) {
+            Some('X') => Some(Size::Bit),
+            Some('B') => Some(Size::Byte),
+            Some('W') => Some(Size::Word),
+            Some('D') => Some(Size::DoubleWord),
+            Some('L') => Some(Size::LongWord),
+            Some(_) | None => None,
+        };
+        if size.is_some() {
+            code_clone.peel(1)?;
+        }
+
+        let location = if let Ok(code_stripped) = code_clone.strip_prefix('*') {
+            code_clone = code_stripped;
+            Location::Unspecified
+        } else {
+            let mut numbers = Vec::new();
+            loop {
+                let digits: String = code_clone
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                let number = digits.parse().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Cannot parse address number\n{code}"),
+                    )
+                })?;
+                code_clone.peel(digits.len())?;
+                numbers.push(number);
+
+                match code_clone.strip_prefix('.') {
+                    Ok(code_stripped)
+                        if code_stripped
+                            .chars()
+                            .next()
+                            .is_some_and(|c| c.is_ascii_digit()) =>
+                    {
+                        code_clone = code_stripped
+                    }
+                    _ => break,
+                }
+            }
+            Location::Numbers(numbers)
+        };
+
+        *code = code_clone;
+        Ok(Self {
+            area,
+            size,
+            location,
+            spelling,
+        })
     }
 }
 
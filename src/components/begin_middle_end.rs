@@ -1,19 +1,26 @@
 use std::io::Result;
 
 use crate::code::Code;
-use crate::dazzle::{self, Dazzle};
+use crate::dazzle::{self, Dazzle, PreviousCharacter};
 
 use super::{Component as C, Ether};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BeginMiddleEnd {
     pub begin: Vec<C>,
     pub middle: Vec<Vec<C>>,
     pub end: Vec<C>,
+    /// Print the middle on the same line as the beginning, if it fits.
+    pub inline: bool,
 }
 
 impl Dazzle for BeginMiddleEnd {
     fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        if self.inline && self.fits_inline(dazzler) {
+            dazzle_inline(self, dazzler);
+            return;
+        }
+
         for b in &self.begin {
             b.dazzle(dazzler);
         }
@@ -33,7 +40,41 @@ impl Dazzle for BeginMiddleEnd {
     }
 }
 
+fn dazzle_inline(begin_middle_end: &BeginMiddleEnd, dazzler: &mut dazzle::Dazzler) {
+    for b in &begin_middle_end.begin {
+        b.dazzle(dazzler);
+    }
+    if dazzler.previous_character == PreviousCharacter::Other {
+        dazzler.previous_character = PreviousCharacter::PendingSpace;
+    }
+    for m in begin_middle_end.middle.iter().flatten() {
+        m.dazzle(dazzler);
+    }
+    for e in &begin_middle_end.end {
+        e.dazzle(dazzler);
+    }
+    dazzler.if_not_linefeed_then_linefeed();
+}
+
 impl BeginMiddleEnd {
+    pub fn keyword(&self) -> Option<&'static str> {
+        self.begin
+            .iter()
+            .find_map(|component| match component {
+                C::Ether(_) | C::Space => None,
+                C::Uppercase(keyword, _) => Some(*keyword),
+                _ => Some(""),
+            })
+            .filter(|keyword| !keyword.is_empty())
+    }
+
+    fn fits_inline(&self, dazzler: &dazzle::Dazzler) -> bool {
+        let mut dazzler_line = dazzler.on_current_line();
+        dazzle_inline(self, &mut dazzler_line);
+        let line = dazzler_line.f.trim_end_matches('\n');
+        !line.contains('\n') && dazzle::width(line) <= crate::fmt::LINE_LENGTH_LIMIT
+    }
+
     pub fn peel(
         code: &mut Code,
         layout_begin: impl Fn(&mut Code) -> Result<Vec<C>>,
@@ -60,6 +101,9 @@ impl BeginMiddleEnd {
         let mut middle = vec![middle_start_ethers];
         let mut code_clone_clone = code_clone.clone();
         while let Ok(mut items) = layout_middle(&mut code_clone_clone) {
+            if code_clone_clone.position() == code_clone.position() {
+                break;
+            }
             for ether in Ether::peel(&mut code_clone_clone)? {
                 items.push(C::Ether(ether));
             }
@@ -83,6 +127,14 @@ impl BeginMiddleEnd {
 
         *code = code_clone;
 
-        Ok((Self { begin, middle, end }, output_after_ethers))
+        Ok((
+            Self {
+                begin,
+                middle,
+                end,
+                inline: false,
+            },
+            output_after_ethers,
+        ))
     }
 }
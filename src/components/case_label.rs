@@ -0,0 +1,63 @@
+use std::io::Result;
+
+use crate::code::Code;
+use crate::dazzle::{self, Dazzle};
+
+use super::Expression;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CaseLabel {
+    Single(Expression),
+    Range(Expression, Expression),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaseLabels(pub Vec<CaseLabel>);
+
+impl Dazzle for CaseLabels {
+    fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        for (i, label) in self.0.iter().enumerate() {
+            match label {
+                CaseLabel::Single(value) => value.dazzle(dazzler),
+                CaseLabel::Range(lower, upper) => {
+                    lower.dazzle(dazzler);
+                    dazzler.f.push_str("..");
+                    dazzler.previous_character = dazzle::PreviousCharacter::Other;
+                    upper.dazzle(dazzler);
+                }
+            }
+            if i + 1 < self.0.len() {
+                dazzler.f.push(',');
+                dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+            }
+        }
+    }
+}
+
+impl CaseLabels {
+    pub fn peel(code: &mut Code) -> Result<Self> {
+        let mut code_clone = code.clone();
+        let mut labels = Vec::new();
+        loop {
+            let lower = Expression::peel(&mut code_clone)?;
+            if let Ok(code_stripped) = code_clone.trim_start().strip_prefix_str("..") {
+                code_clone = code_stripped.trim_start();
+                let upper = Expression::peel(&mut code_clone)?;
+                labels.push(CaseLabel::Range(lower, upper));
+            } else {
+                labels.push(CaseLabel::Single(lower));
+            }
+
+            match code_clone.trim_start().strip_prefix(',') {
+                Ok(code_stripped) => code_clone = code_stripped.trim_start(),
+                Err(_) => break,
+            }
+        }
+        *code = code_clone;
+        Ok(Self(labels))
+    }
+}
+
+#[cfg(test)]
+#[path = "./test_case_label.rs"]
+mod test_case_label;
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// A conditional compilation pragma, such as `{IF defined (pou:MC_Power)}`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Conditional {
+    If(Condition),
+    ElsIf(Condition),
+    Else,
+    EndIf,
+}
+
+/// The condition of an `{IF ...}` or `{ELSIF ...}` pragma.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// `defined (name)`, where a scoped name such as `pou:MC_Power` is held without spaces.
+    Defined(String),
+    /// `hasvalue (name, 'value')`.
+    HasValue(String, String),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Bracket(Box<Condition>),
+    /// Any other operator, such as `hasattribute (...)`, as written.
+    Other(String),
+}
+
+/// The defines to evaluate conditions against, by name, with their values. Scoped names such as
+/// `pou:MC_Power` stand for the existence of that POU, variable or type.
+pub type Defines = HashMap<String, String>;
+
+impl fmt::Display for Conditional {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::If(condition) => write!(f, "IF {condition}"),
+            Self::ElsIf(condition) => write!(f, "ELSIF {condition}"),
+            Self::Else => write!(f, "ELSE"),
+            Self::EndIf => write!(f, "END_IF"),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Defined(name) => write!(f, "defined ({name})"),
+            Self::HasValue(name, value) => write!(f, "hasvalue ({name}, '{value}')"),
+            Self::Not(inner) => write!(f, "NOT {inner}"),
+            Self::And(left, right) => write!(f, "{left} AND {right}"),
+            Self::Or(left, right) => write!(f, "{left} OR {right}"),
+            Self::Bracket(inner) => write!(f, "({inner})"),
+            Self::Other(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl Conditional {
+    /// Recognises `IF` or `ELSIF` with a condition, or a bare `ELSE` or `END_IF`, in any case.
+    pub fn parse(pragma: &str) -> Option<Self> {
+        let (keyword, rest) = match pragma.split_once(char::is_whitespace) {
+            Some((keyword, rest)) => (keyword, rest.trim()),
+            None => (pragma, ""),
+        };
+        match keyword.to_uppercase().as_str() {
+            "IF" if !rest.is_empty() => Some(Self::If(Condition::parse(rest))),
+            "ELSIF" if !rest.is_empty() => Some(Self::ElsIf(Condition::parse(rest))),
+            "ELSE" if rest.is_empty() => Some(Self::Else),
+            "END_IF" if rest.is_empty() => Some(Self::EndIf),
+            _ => None,
+        }
+    }
+}
+
+impl Condition {
+    fn parse(text: &str) -> Self {
+        let mut parser = Parser {
+            text,
+            tokens: tokenize(text),
+            i: 0,
+        };
+        match parser.or() {
+            Some(condition) if parser.i == parser.tokens.len() => condition,
+            _ => Self::Other(text.to_string()),
+        }
+    }
+
+    /// Whether the condition holds for `defines`. Operators other than `defined` and `hasvalue`
+    /// never hold.
+    pub fn evaluate(&self, defines: &Defines) -> bool {
+        let lookup = |name: &str| {
+            defines
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        };
+        match self {
+            Self::Defined(name) => lookup(name).is_some(),
+            Self::HasValue(name, value) => lookup(name).is_some_and(|v| v == value),
+            Self::Not(inner) => !inner.evaluate(defines),
+            Self::And(left, right) => left.evaluate(defines) && right.evaluate(defines),
+            Self::Or(left, right) => left.evaluate(defines) || right.evaluate(defines),
+            Self::Bracket(inner) => inner.evaluate(defines),
+            Self::Other(_) => false,
+        }
+    }
+}
+
+/// Follows conditional pragmas in source order to tell whether the code between them is compiled
+/// for a set of defines.
+#[derive(Clone, Debug)]
+pub struct Conditions<'a> {
+    defines: &'a Defines,
+    /// For each open `{IF}`, whether the current branch is taken and whether any branch was.
+    branches: Vec<(bool, bool)>,
+}
+
+impl<'a> Conditions<'a> {
+    pub fn new(defines: &'a Defines) -> Self {
+        Self {
+            defines,
+            branches: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, conditional: &Conditional) {
+        match conditional {
+            Conditional::If(condition) => {
+                let taken = condition.evaluate(self.defines);
+                self.branches.push((taken, taken));
+            }
+            Conditional::ElsIf(condition) => {
+                if let Some((taken, any_taken)) = self.branches.last_mut() {
+                    *taken = !*any_taken && condition.evaluate(self.defines);
+                    *any_taken |= *taken;
+                }
+            }
+            Conditional::Else => {
+                if let Some((taken, any_taken)) = self.branches.last_mut() {
+                    *taken = !*any_taken;
+                    *any_taken = true;
+                }
+            }
+            Conditional::EndIf => {
+                self.branches.pop();
+            }
+        }
+    }
+
+    /// Whether every enclosing branch is taken.
+    pub fn is_active(&self) -> bool {
+        self.branches.iter().all(|(taken, _)| *taken)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Symbol(char),
+}
+
+/// Splits `text` into tokens, each with the byte range it was read from.
+fn tokenize(text: &str) -> Vec<(Token, Range<usize>)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = if c.is_whitespace() {
+            continue;
+        } else if c == '\'' {
+            let quoted: String = chars
+                .by_ref()
+                .map(|(_, d)| d)
+                .take_while(|d| *d != '\'')
+                .collect();
+            Token::Quoted(quoted)
+        } else if is_word(c) {
+            let mut word = String::from(c);
+            while let Some((_, d)) = chars.next_if(|(_, d)| is_word(*d)) {
+                word.push(d);
+            }
+            Token::Word(word)
+        } else {
+            Token::Symbol(c)
+        };
+        let end = chars.peek().map_or(text.len(), |(i, _)| *i);
+        tokens.push((token, start..end));
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<(Token, Range<usize>)>,
+    i: usize,
+}
+
+impl Parser<'_> {
+    fn or(&mut self) -> Option<Condition> {
+        let mut left = self.and()?;
+        while self.keyword("OR") {
+            left = Condition::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Some(left)
+    }
+
+    fn and(&mut self) -> Option<Condition> {
+        let mut left = self.not()?;
+        while self.keyword("AND") {
+            left = Condition::And(Box::new(left), Box::new(self.not()?));
+        }
+        Some(left)
+    }
+
+    fn not(&mut self) -> Option<Condition> {
+        if self.keyword("NOT") {
+            return Some(Condition::Not(Box::new(self.not()?)));
+        }
+        if self.symbol('(') {
+            let inner = self.or()?;
+            return self
+                .symbol(')')
+                .then(|| Condition::Bracket(Box::new(inner)));
+        }
+        let Some((Token::Word(operator), range)) = self.tokens.get(self.i).cloned() else {
+            return None;
+        };
+        self.i += 1;
+        if !self.symbol('(') {
+            return None;
+        }
+        let start = self.i;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.tokens.get(self.i)?.0 {
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') => depth -= 1,
+                _ => (),
+            }
+            self.i += 1;
+        }
+        let arguments: Vec<&Token> = self.tokens[start..self.i - 1]
+            .iter()
+            .map(|(token, _)| token)
+            .collect();
+        let end = self.tokens[self.i - 1].1.end;
+        Some(
+            match (operator.to_lowercase().as_str(), arguments.as_slice()) {
+                ("defined", [Token::Word(name)]) => Condition::Defined(name.clone()),
+                ("defined", [Token::Word(scope), Token::Symbol(':'), Token::Word(name)]) => {
+                    Condition::Defined(format!("{scope}:{name}"))
+                }
+                ("hasvalue", [Token::Word(name), Token::Symbol(','), Token::Quoted(value)]) => {
+                    Condition::HasValue(name.clone(), value.clone())
+                }
+                _ => Condition::Other(self.text[range.start..end].to_string()),
+            },
+        )
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.tokens.get(self.i),
+            Some((Token::Word(word), _)) if word.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.i += 1;
+        }
+        found
+    }
+
+    fn symbol(&mut self, symbol: char) -> bool {
+        let found = self
+            .tokens
+            .get(self.i)
+            .is_some_and(|(token, _)| *token == Token::Symbol(symbol));
+        if found {
+            self.i += 1;
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+#[path = "./test_conditional.rs"]
+mod test_conditional;
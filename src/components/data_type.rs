@@ -1,42 +1,227 @@
 use std::fmt;
 use std::io::{Error, ErrorKind, Result};
-use std::str::FromStr;
 
 use crate::code::Code;
+use crate::dazzle::{self, Dazzle};
+use crate::fmt::KeywordCase;
 
-use super::Identifier;
+use super::{Expression, Identifier, Spelling};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DataType {
-    Array(ArrayRange, Box<DataType>),
-    String(Option<u16>),
-    ReferenceTo(Box<DataType>),
-    PointerTo(Box<DataType>),
+    /// An array, with the spellings of `ARRAY` and `OF`.
+    Array(Vec<ArrayRange>, Box<DataType>, Vec<Spelling>),
+    /// `STRING`, with an optional length such as `STRING(80)` or `STRING[GVL.MAX_LEN]`.
+    String(Option<Expression>, Spelling),
+    WString(Option<Expression>, Spelling),
+    ReferenceTo(Box<DataType>, Spelling),
+    PointerTo(Box<DataType>, Spelling),
     ImplicitEnum(Vec<Identifier>),
+    /// An integer type limited to a range of values, such as `INT(0..100)`.
+    Subrange(Box<DataType>, Expression, Expression),
+    /// An elementary type, with the keyword as written, such as `TIME_OF_DAY` or `TOD`.
+    Elementary(Elementary, Spelling),
+    /// A user defined type, such as a structure, enumeration or function block.
     Flat(String),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Elementary {
+    Bool,
+    Bit,
+    Byte,
+    Word,
+    DWord,
+    LWord,
+    XWord,
+    SInt,
+    Int,
+    DInt,
+    LInt,
+    USInt,
+    UInt,
+    UDInt,
+    ULInt,
+    XInt,
+    UXInt,
+    Real,
+    LReal,
+    Time,
+    LTime,
+    Date,
+    TimeOfDay,
+    DateAndTime,
+    Any,
+    AnyDerived,
+    AnyElementary,
+    AnyMagnitude,
+    AnyNum,
+    AnyReal,
+    AnyInt,
+    AnyBit,
+    AnyString,
+    AnyDate,
+}
+
+impl Elementary {
+    /// Every type with its canonical keyword, followed by any other spellings.
+    const KEYWORDS: &'static [(Self, &'static str)] = &[
+        (Self::Bool, "BOOL"),
+        (Self::Bit, "BIT"),
+        (Self::Byte, "BYTE"),
+        (Self::Word, "WORD"),
+        (Self::DWord, "DWORD"),
+        (Self::LWord, "LWORD"),
+        (Self::XWord, "__XWORD"),
+        (Self::SInt, "SINT"),
+        (Self::Int, "INT"),
+        (Self::DInt, "DINT"),
+        (Self::LInt, "LINT"),
+        (Self::USInt, "USINT"),
+        (Self::UInt, "UINT"),
+        (Self::UDInt, "UDINT"),
+        (Self::ULInt, "ULINT"),
+        (Self::XInt, "__XINT"),
+        (Self::UXInt, "__UXINT"),
+        (Self::Real, "REAL"),
+        (Self::LReal, "LREAL"),
+        (Self::Time, "TIME"),
+        (Self::LTime, "LTIME"),
+        (Self::Date, "DATE"),
+        (Self::TimeOfDay, "TOD"),
+        (Self::DateAndTime, "DT"),
+        (Self::Any, "ANY"),
+        (Self::AnyDerived, "ANY_DERIVED"),
+        (Self::AnyElementary, "ANY_ELEMENTARY"),
+        (Self::AnyMagnitude, "ANY_MAGNITUDE"),
+        (Self::AnyNum, "ANY_NUM"),
+        (Self::AnyReal, "ANY_REAL"),
+        (Self::AnyInt, "ANY_INT"),
+        (Self::AnyBit, "ANY_BIT"),
+        (Self::AnyString, "ANY_STRING"),
+        (Self::AnyDate, "ANY_DATE"),
+        (Self::TimeOfDay, "TIME_OF_DAY"),
+        (Self::DateAndTime, "DATE_AND_TIME"),
+    ];
+
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        Self::KEYWORDS
+            .iter()
+            .find(|(_, k)| k.eq_ignore_ascii_case(keyword))
+            .map(|(elementary, _)| *elementary)
+    }
+
+    pub fn keyword(&self) -> &'static str {
+        Self::KEYWORDS
+            .iter()
+            .find(|(elementary, _)| elementary == self)
+            .map_or("", |(_, keyword)| keyword)
+    }
+}
+
+impl fmt::Display for Elementary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.keyword())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum ArrayRange {
-    LowerUpper(String, String),
+    LowerUpper(Expression, Expression),
     Star,
 }
 
+impl ArrayRange {
+    /// The lower bound, or `None` for `*`.
+    pub fn lower(&self) -> Option<&Expression> {
+        match self {
+            Self::LowerUpper(lower, _) => Some(lower),
+            Self::Star => None,
+        }
+    }
+
+    /// The upper bound, or `None` for `*`.
+    pub fn upper(&self) -> Option<&Expression> {
+        match self {
+            Self::LowerUpper(_, upper) => Some(upper),
+            Self::Star => None,
+        }
+    }
+}
+
+impl fmt::Display for ArrayRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LowerUpper(lower, upper) => {
+                write!(f, "{}..{}", dazzled(lower), dazzled(upper))
+            }
+            Self::Star => write!(f, "*"),
+        }
+    }
+}
+
+fn dazzled(expression: &Expression) -> String {
+    dazzled_cased(expression, KeywordCase::default())
+}
+
+fn dazzled_cased(expression: &Expression, keyword_case: KeywordCase) -> String {
+    let mut dazzler = dazzle::Dazzler {
+        keyword_case,
+        ..Default::default()
+    };
+    expression.dazzle(&mut dazzler);
+    dazzler.f
+}
+
 impl fmt::Display for DataType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_cased(f, KeywordCase::default())
+    }
+}
+
+impl DataType {
+    /// Writes the type with its keywords, such as `ARRAY`, `OF` and `INT`, spelled as `case` asks.
+    pub fn write_cased(&self, f: &mut dyn fmt::Write, case: KeywordCase) -> fmt::Result {
         match self {
-            Self::Array(range, flat) => match range {
-                ArrayRange::LowerUpper(lower, upper) => {
-                    write!(f, "ARRAY [{lower}..{upper}] OF {flat}")
+            Self::Array(ranges, flat, spellings) => {
+                let spelling = |i: usize| spellings.get(i).cloned().unwrap_or_default();
+                write!(f, "{} [", case.apply("ARRAY", &spelling(0)))?;
+                for (i, range) in ranges.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match range {
+                        ArrayRange::LowerUpper(lower, upper) => write!(
+                            f,
+                            "{}..{}",
+                            dazzled_cased(lower, case),
+                            dazzled_cased(upper, case)
+                        )?,
+                        ArrayRange::Star => write!(f, "*")?,
+                    }
                 }
-                ArrayRange::Star => write!(f, "ARRAY [*] OF {flat}"),
-            },
-            Self::String(inner) => match inner {
-                Some(length) => write!(f, "STRING({length})"),
-                None => write!(f, "STRING"),
-            },
-            Self::ReferenceTo(inner) => write!(f, "REFERENCE TO {inner}"),
-            Self::PointerTo(inner) => write!(f, "POINTER TO {inner}"),
+                write!(f, "] {} ", case.apply("OF", &spelling(1)))?;
+                flat.write_cased(f, case)
+            }
+            Self::String(length, spelling) | Self::WString(length, spelling) => {
+                let keyword = match self {
+                    Self::WString(..) => "WSTRING",
+                    _ => "STRING",
+                };
+                write!(f, "{}", case.apply(keyword, spelling))?;
+                match length {
+                    Some(length) => write!(f, "({})", dazzled_cased(length, case)),
+                    None => Ok(()),
+                }
+            }
+            Self::ReferenceTo(inner, spelling) => {
+                write!(f, "{} ", case.apply("REFERENCE TO", spelling))?;
+                inner.write_cased(f, case)
+            }
+            Self::PointerTo(inner, spelling) => {
+                write!(f, "{} ", case.apply("POINTER TO", spelling))?;
+                inner.write_cased(f, case)
+            }
             Self::ImplicitEnum(inner) => {
                 write!(f, "(")?;
                 for (i, member) in inner.iter().enumerate() {
@@ -48,6 +233,21 @@ impl fmt::Display for DataType {
                 write!(f, ")")?;
                 Ok(())
             }
+            Self::Subrange(inner, lower, upper) => {
+                inner.write_cased(f, case)?;
+                write!(
+                    f,
+                    "({}..{})",
+                    dazzled_cased(lower, case),
+                    dazzled_cased(upper, case)
+                )
+            }
+            Self::Elementary(inner, spelling) if spelling.0.is_empty() => {
+                write!(f, "{}", case.apply(inner.keyword(), spelling))
+            }
+            Self::Elementary(_, spelling) => {
+                write!(f, "{}", case.apply(&spelling.0.to_uppercase(), spelling))
+            }
             Self::Flat(inner) => write!(f, "{inner}"),
         }
     }
@@ -60,15 +260,17 @@ impl DataType {
         } else if let Ok(s) = Self::peel_string(code) {
             Ok(s)
         } else if let Ok(mut code_clone) = code.strip_prefix_uppercase("REFERENCE TO") {
+            let spelling = Spelling::between(code, &code_clone);
             code_clone = code_clone.trim_start();
             let flat = Self::peel(&mut code_clone)?;
             *code = code_clone;
-            Ok(Self::ReferenceTo(Box::new(flat)))
+            Ok(Self::ReferenceTo(Box::new(flat), spelling))
         } else if let Ok(mut code_clone) = code.strip_prefix_uppercase("POINTER TO") {
+            let spelling = Spelling::between(code, &code_clone);
             code_clone = code_clone.trim_start();
             let flat = Self::peel(&mut code_clone)?;
             *code = code_clone;
-            Ok(Self::PointerTo(Box::new(flat)))
+            Ok(Self::PointerTo(Box::new(flat), spelling))
         } else if let Ok(implicit_enum) = Self::peel_implicit_enum(code) {
             Ok(implicit_enum)
         } else {
@@ -87,63 +289,89 @@ impl DataType {
                 ))
             } else {
                 code.peel(data_type.len())?;
-                Ok(Self::Flat(data_type))
+                let flat = match Elementary::from_keyword(&data_type) {
+                    Some(elementary) => Self::Elementary(elementary, Spelling(data_type)),
+                    None => Self::Flat(data_type),
+                };
+                match Self::peel_subrange(code) {
+                    Ok((lower, upper)) => Ok(Self::Subrange(Box::new(flat), lower, upper)),
+                    Err(_) => Ok(flat),
+                }
             }
         }
     }
 
+    /// The dimensions of an array type, or `None` for other types.
+    pub fn dimensions(&self) -> Option<&[ArrayRange]> {
+        match self {
+            Self::Array(ranges, ..) => Some(ranges),
+            _ => None,
+        }
+    }
+
     fn peel_array(code: &mut Code) -> Result<Self> {
-        let mut code_clone = code.strip_prefix_uppercase("ARRAY")?.trim_start();
+        let code_array = code.strip_prefix_keyword("ARRAY")?;
+        let mut spellings = vec![Spelling::between(code, &code_array)];
+        let mut code_clone = code_array.trim_start().strip_prefix('[')?.trim_start();
 
-        let range_string = code_clone.strip_between_and_trim_inner("[", "]")?;
-        let range = if range_string == "*" {
-            ArrayRange::Star
-        } else {
-            match range_string.split_once("..") {
-                Some((start, end)) => {
-                    ArrayRange::LowerUpper(start.trim().to_string(), end.trim().to_string())
-                }
-                None => {
-                    return Err(Error::new(
-                        ErrorKind::InvalidData,
-                        format!("Cannot parse array range\n{code}"),
-                    ))
-                }
+        let mut ranges = Vec::new();
+        loop {
+            if let Ok(code_stripped) = code_clone.strip_prefix('*') {
+                code_clone = code_stripped.trim_start();
+                ranges.push(ArrayRange::Star);
+            } else {
+                let (lower, upper) = peel_range(&mut code_clone)?;
+                ranges.push(ArrayRange::LowerUpper(lower, upper));
             }
-        };
+            if let Ok(code_stripped) = code_clone.strip_prefix(',') {
+                code_clone = code_stripped.trim_start();
+            } else {
+                break;
+            }
+        }
 
-        code_clone = code_clone
-            .trim_start()
-            .strip_prefix_uppercase("OF")?
-            .trim_start();
+        code_clone = code_clone.strip_prefix(']')?.trim_start();
+        let code_of = code_clone.strip_prefix_keyword("OF")?;
+        spellings.push(Spelling::between(&code_clone, &code_of));
+        code_clone = code_of.trim_start();
 
         let flat = Self::peel(&mut code_clone)?;
 
         *code = code_clone;
-        Ok(Self::Array(range, Box::new(flat)))
+        Ok(Self::Array(ranges, Box::new(flat), spellings))
     }
 
     fn peel_string(code: &mut Code) -> Result<Self> {
-        let mut code_clone = code.strip_prefix_uppercase("STRING")?.trim_start();
-        match code_clone.strip_between_and_trim_inner("(", ")") {
-            Ok(inner) => {
-                let length = match u16::from_str(&inner) {
-                    Ok(i) => i,
-                    Err(_) => {
-                        return Err(Error::new(
-                            ErrorKind::InvalidData,
-                            format!("Cannot parse STRING length\n{code}"),
-                        ))
-                    }
-                };
-                *code = code_clone;
-                Ok(Self::String(Some(length)))
-            }
-            Err(_) => {
-                *code = code_clone;
-                Ok(Self::String(None))
+        let (wide, mut code_clone) = match code.strip_prefix_keyword("WSTRING") {
+            Ok(code_stripped) => (true, code_stripped),
+            Err(_) => (false, code.strip_prefix_keyword("STRING")?),
+        };
+        let spelling = Spelling::between(code, &code_clone);
+
+        let mut length = None;
+        let code_trimmed = code_clone.trim_start();
+        for (open, close) in [('(', ')'), ('[', ']')] {
+            if let Ok(mut code_inner) = code_trimmed.strip_prefix(open) {
+                code_inner = code_inner.trim_start();
+                length = Some(Expression::peel(&mut code_inner)?);
+                code_clone = code_inner.trim_start().strip_prefix(close)?;
+                break;
             }
         }
+
+        *code = code_clone;
+        if wide {
+            Ok(Self::WString(length, spelling))
+        } else {
+            Ok(Self::String(length, spelling))
+        }
+    }
+
+    fn peel_subrange(code: &mut Code) -> Result<(Expression, Expression)> {
+        let mut code_clone = code.trim_start().strip_prefix('(')?.trim_start();
+        let range = peel_range(&mut code_clone)?;
+        *code = code_clone.strip_prefix(')')?;
+        Ok(range)
     }
 
     fn peel_implicit_enum(code: &mut Code) -> Result<Self> {
@@ -171,6 +399,15 @@ impl DataType {
     }
 }
 
+fn peel_range(code: &mut Code) -> Result<(Expression, Expression)> {
+    let mut code_clone = code.clone();
+    let lower = Expression::peel(&mut code_clone)?;
+    code_clone = code_clone.trim_start().strip_prefix_str("..")?.trim_start();
+    let upper = Expression::peel(&mut code_clone)?;
+    *code = code_clone.trim_start();
+    Ok((lower, upper))
+}
+
 #[cfg(test)]
 #[path = "./test_data_type.rs"]
 mod test_data_type;
@@ -1,8 +1,11 @@
+use std::fmt;
 use std::io::Result;
 
 use crate::code::Code;
 use crate::dazzle::{self, Dazzle, PreviousCharacter};
 
+use super::Conditional;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Ether {
     LineFeed,
@@ -15,8 +18,54 @@ pub struct PragmaOrComment(PragmaOrCommentInner);
 #[derive(Clone, Debug, PartialEq)]
 enum PragmaOrCommentInner {
     Pragma(String),
+    Attribute(Attribute),
+    Conditional(Conditional),
     CommentSingleLine(String),
     CommentMultiLine(String),
+    /// A `(* *)` comment spanning several lines, kept as written, with the column its `(*` was
+    /// found at.
+    CommentBlock(String, usize),
+}
+
+/// An attribute pragma, such as `{attribute 'hide'}` or `{attribute 'pack_mode' := '1'}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attribute {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "attribute '{}'", self.name)?;
+        if let Some(value) = &self.value {
+            write!(f, " := '{value}'")?;
+        }
+        Ok(())
+    }
+}
+
+impl Attribute {
+    /// Recognises `attribute 'name'` or `attribute 'name' := 'value'`, with the keyword in any
+    /// case, from between the braces of a pragma.
+    pub fn parse(pragma: &str) -> Option<Self> {
+        let (keyword, rest) = pragma.trim().split_at_checked("attribute".len())?;
+        if !keyword.eq_ignore_ascii_case("attribute") {
+            return None;
+        }
+        let rest = rest.trim_start();
+        let (name, rest) = rest.strip_prefix('\'')?.split_once('\'')?;
+        let rest = rest.trim();
+        let value = if rest.is_empty() {
+            None
+        } else {
+            let value = rest.strip_prefix(":=")?.trim();
+            Some(value.strip_prefix('\'')?.strip_suffix('\'')?.to_string())
+        };
+        Some(Self {
+            name: name.to_string(),
+            value,
+        })
+    }
 }
 
 impl Dazzle for Ether {
@@ -31,6 +80,35 @@ impl Dazzle for Ether {
                 dazzler.f.push_str(&format!("{{{inner}}}"));
                 dazzler.previous_character = PreviousCharacter::Other;
             }
+            Self::PragmaOrComment(PragmaOrComment(PragmaOrCommentInner::Attribute(inner))) => {
+                dazzler.if_not_linefeed_then_linefeed();
+                dazzler.indent();
+                dazzler.f.push_str(&format!("{{{inner}}}"));
+                dazzler.previous_character = PreviousCharacter::Other;
+            }
+            Self::PragmaOrComment(PragmaOrComment(PragmaOrCommentInner::Conditional(inner))) => {
+                dazzler.if_not_linefeed_then_linefeed();
+                let level = match inner {
+                    Conditional::If(_) => {
+                        let level = dazzler.indentation();
+                        dazzler.conditionals.push(level);
+                        level
+                    }
+                    Conditional::ElsIf(_) | Conditional::Else => dazzler
+                        .conditionals
+                        .last()
+                        .copied()
+                        .unwrap_or(dazzler.indentation()),
+                    Conditional::EndIf => {
+                        dazzler.conditionals.pop().unwrap_or(dazzler.indentation())
+                    }
+                };
+                for _ in 0..level {
+                    dazzler.f.push_str("    ");
+                }
+                dazzler.f.push_str(&format!("{{{inner}}}"));
+                dazzler.previous_character = PreviousCharacter::Other;
+            }
             Self::PragmaOrComment(PragmaOrComment(PragmaOrCommentInner::CommentSingleLine(
                 inner,
             ))) => {
@@ -46,13 +124,131 @@ impl Dazzle for Ether {
                 inner,
             ))) => {
                 dazzler.indent_or_space(true);
-                dazzler.f.push_str(&format!("(* {inner} *)"));
+                if !(dazzler.reflow_comments && dazzle_reflowed(inner, dazzler)) {
+                    dazzler.f.push_str(&format!("(* {inner} *)"));
+                }
+                dazzler.previous_character = PreviousCharacter::Other;
+            }
+            Self::PragmaOrComment(PragmaOrComment(PragmaOrCommentInner::CommentBlock(
+                inner,
+                column,
+            ))) => {
+                dazzler.indent_or_space(true);
+                if !(dazzler.reflow_comments && dazzle_reflowed(inner, dazzler)) {
+                    dazzle_shifted(inner, *column, dazzler);
+                }
                 dazzler.previous_character = PreviousCharacter::Other;
             }
         }
     }
 }
 
+fn current_column(dazzler: &dazzle::Dazzler) -> usize {
+    dazzle::width(dazzler.f.rsplit('\n').next().unwrap_or_default())
+}
+
+/// Writes a block comment with every line after the first moved by as much as its `(*` has moved.
+fn dazzle_shifted(inner: &str, column: usize, dazzler: &mut dazzle::Dazzler) {
+    let shift = current_column(dazzler) as isize - column as isize;
+    let lines: Vec<&str> = inner.split('\n').collect();
+    dazzler.f.push_str("(*");
+    dazzler.f.push_str(lines[0].trim_end());
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        dazzler.f.push('\n');
+        let is_last = i + 1 == lines.len();
+        if line.trim().is_empty() && !is_last {
+            continue;
+        }
+        if shift >= 0 {
+            dazzler.f.push_str(&" ".repeat(shift as usize));
+            dazzler.f.push_str(line);
+        } else {
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            dazzler
+                .f
+                .push_str(&line[spaces.min(shift.unsigned_abs())..]);
+        }
+        if !is_last {
+            dazzler.f.truncate(dazzler.f.trim_end().len());
+        }
+    }
+    dazzler.f.push_str("*)");
+}
+
+/// Refills a block comment of prose to the line length limit, or gives `false` if the comment does
+/// not read as prose, such as a diagram, a table or commented-out code.
+fn dazzle_reflowed(inner: &str, dazzler: &mut dazzle::Dazzler) -> bool {
+    let Some(paragraphs) = paragraphs(inner) else {
+        return false;
+    };
+    let margin = " ".repeat(current_column(dazzler) + "(*".len());
+    let limit = crate::fmt::LINE_LENGTH_LIMIT;
+    let mut width = margin.len();
+    dazzler.f.push_str("(*");
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        if i > 0 {
+            dazzler.f.push_str("\n\n");
+            dazzler.f.push_str(&margin);
+            width = margin.len();
+        }
+        let is_last_paragraph = i + 1 == paragraphs.len();
+        let words = paragraph
+            .iter()
+            .copied()
+            .chain(is_last_paragraph.then_some("*)"));
+        for (j, word) in words.enumerate() {
+            let word_width = dazzle::width(word);
+            if j > 0 && width + 1 + word_width > limit {
+                dazzler.f.push('\n');
+                dazzler.f.push_str(&margin);
+                width = margin.len();
+            }
+            dazzler.f.push(' ');
+            dazzler.f.push_str(word);
+            width += 1 + word_width;
+        }
+    }
+    true
+}
+
+/// The words of each blank-line-separated paragraph of a prose comment, or `None` if a line is
+/// indented differently, has aligned columns, ends in `;` or holds a token with no letters or
+/// digits, such as `|` or `:=`.
+fn paragraphs(inner: &str) -> Option<Vec<Vec<&str>>> {
+    let mut indentation = None;
+    let mut paragraphs = Vec::new();
+    let mut paragraph = Vec::new();
+    for (i, line) in inner.split('\n').enumerate() {
+        let text = line.trim();
+        if text.is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(std::mem::take(&mut paragraph));
+            }
+            continue;
+        }
+        if text.contains("  ")
+            || text.contains('\t')
+            || text.ends_with(';')
+            || text
+                .split(' ')
+                .any(|word| !word.contains(char::is_alphanumeric))
+        {
+            return None;
+        }
+        if i > 0 {
+            let this_indentation = line.len() - line.trim_start().len();
+            if *indentation.get_or_insert(this_indentation) != this_indentation {
+                return None;
+            }
+        }
+        paragraph.extend(text.split(' '));
+    }
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph);
+    }
+    (!paragraphs.is_empty()).then_some(paragraphs)
+}
+
 impl Ether {
     pub fn peel(code: &mut Code) -> Result<Vec<Self>> {
         let mut pragmas_and_comments = Vec::new();
@@ -89,16 +285,25 @@ fn peel_new_line(pragmas_and_comments: &mut Vec<Ether>, code: &mut Code) -> Resu
 
 fn peel_single(pragmas_and_comments: &mut Vec<Ether>, code: &mut Code) -> Result<bool> {
     if let Ok(pragma) = code.strip_between_nestable_and_trim_inner("{", "}") {
-        pragmas_and_comments.push(Ether::PragmaOrComment(PragmaOrComment(
-            PragmaOrCommentInner::Pragma(pragma),
-        )));
+        let inner = if let Some(attribute) = Attribute::parse(&pragma) {
+            PragmaOrCommentInner::Attribute(attribute)
+        } else if let Some(conditional) = Conditional::parse(&pragma) {
+            PragmaOrCommentInner::Conditional(conditional)
+        } else {
+            PragmaOrCommentInner::Pragma(pragma)
+        };
+        pragmas_and_comments.push(Ether::PragmaOrComment(PragmaOrComment(inner)));
         return Ok(true);
     }
 
-    if let Ok(comment) = code.strip_between_nestable_and_trim_inner("(*", "*)") {
-        pragmas_and_comments.push(Ether::PragmaOrComment(PragmaOrComment(
-            PragmaOrCommentInner::CommentMultiLine(comment),
-        )));
+    let column = code.column();
+    if let Ok(comment) = code.strip_between_nestable("(*", "*)") {
+        let inner = if comment.contains('\n') {
+            PragmaOrCommentInner::CommentBlock(comment.to_string(), column)
+        } else {
+            PragmaOrCommentInner::CommentMultiLine(comment.trim().to_string())
+        };
+        pragmas_and_comments.push(Ether::PragmaOrComment(PragmaOrComment(inner)));
         return Ok(true);
     }
 
@@ -120,12 +325,45 @@ fn peel_single(pragmas_and_comments: &mut Vec<Ether>, code: &mut Code) -> Result
 }
 
 impl Ether {
+    /// The attribute pragma, such as `{attribute 'pack_mode' := '1'}`, this is.
+    pub fn attribute(&self) -> Option<&Attribute> {
+        match self {
+            Self::PragmaOrComment(PragmaOrComment(PragmaOrCommentInner::Attribute(inner))) => {
+                Some(inner)
+            }
+            _ => None,
+        }
+    }
+
+    /// The conditional compilation pragma, such as `{IF defined (X)}`, this is.
+    pub fn conditional(&self) -> Option<&Conditional> {
+        match self {
+            Self::PragmaOrComment(PragmaOrComment(PragmaOrCommentInner::Conditional(inner))) => {
+                Some(inner)
+            }
+            _ => None,
+        }
+    }
+
+    /// The text of a `//` or `(* *)` comment, without its delimiters.
+    pub fn comment(&self) -> Option<&str> {
+        match self {
+            Self::PragmaOrComment(
+                PragmaOrComment(PragmaOrCommentInner::CommentSingleLine(inner))
+                | PragmaOrComment(PragmaOrCommentInner::CommentMultiLine(inner))
+                | PragmaOrComment(PragmaOrCommentInner::CommentBlock(inner, _)),
+            ) => Some(inner.trim()),
+            _ => None,
+        }
+    }
+
     pub fn is_comment(&self) -> bool {
         matches!(
             self,
             Self::PragmaOrComment(
                 PragmaOrComment(PragmaOrCommentInner::CommentSingleLine(_))
                     | PragmaOrComment(PragmaOrCommentInner::CommentMultiLine(_))
+                    | PragmaOrComment(PragmaOrCommentInner::CommentBlock(..))
             )
         )
     }
@@ -3,13 +3,13 @@ use std::io::{Error, ErrorKind, Result};
 use crate::code::Code;
 use crate::dazzle::{self, Dazzle};
 
-use super::{Ether, Identifier, Member, Value};
+use super::{Ether, Identifier, Member, Spelling, Value, ValueInner};
 
-#[derive(Debug, PartialEq)]
-pub struct Expression(ExpressionInner);
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression(pub ExpressionInner);
 
-#[derive(Debug, PartialEq)]
-enum ExpressionInner {
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionInner {
     BinaryOperator(Box<BinaryOperator>),
     Bracket(Vec<Ether>, Box<ExpressionInner>, Vec<Ether>),
     Dereference(Box<ExpressionInner>),
@@ -18,7 +18,8 @@ enum ExpressionInner {
     Index(Box<ExpressionInner>, Vec<ExpressionInner>),
     Method(Box<ExpressionInner>, Box<FunctionCall>),
     Negative(Box<ExpressionInner>),
-    Not(Vec<Ether>, Box<ExpressionInner>),
+    Positive(Box<ExpressionInner>),
+    Not(Spelling, Vec<Ether>, Box<ExpressionInner>),
     Value(Value),
 }
 
@@ -45,7 +46,7 @@ impl ExpressionInner {
                 for ether in &inner.ethers0 {
                     ether.dazzle(dazzler);
                 }
-                inner.operator.dazzle(dazzler);
+                inner.operator.dazzle_spelled(&inner.spelling, dazzler);
                 for ether in &inner.ethers1 {
                     ether.dazzle(dazzler);
                 }
@@ -125,8 +126,12 @@ impl ExpressionInner {
                 '-'.dazzle(dazzler);
                 inner.dazzle_inner(dazzler, indented);
             }
-            Self::Not(ethers, inner) => {
-                "NOT".dazzle(dazzler);
+            Self::Positive(inner) => {
+                '+'.dazzle(dazzler);
+                inner.dazzle_inner(dazzler, indented);
+            }
+            Self::Not(spelling, ethers, inner) => {
+                dazzler.keyword("NOT", spelling);
                 dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
                 if !indented {
                     dazzler.indentation_count += 1;
@@ -148,21 +153,142 @@ impl Expression {
     pub fn peel(code: &mut Code) -> Result<Self> {
         Ok(Self(ExpressionInner::peel(code)?))
     }
+
+    /// Evaluates a constant integer expression, looking up any names with `resolve`.
+    pub fn evaluate_integer(&self, resolve: &dyn Fn(&str) -> Option<i64>) -> Option<i64> {
+        self.0.evaluate_integer(resolve)
+    }
+}
+
+impl ExpressionInner {
+    fn evaluate_integer(&self, resolve: &dyn Fn(&str) -> Option<i64>) -> Option<i64> {
+        match self {
+            Self::BinaryOperator(inner) => {
+                let left = inner.left.evaluate_integer(resolve)?;
+                let right = inner.right.evaluate_integer(resolve)?;
+                match inner.operator {
+                    Operator::Add => left.checked_add(right),
+                    Operator::Subtract => left.checked_sub(right),
+                    Operator::Multiply => left.checked_mul(right),
+                    Operator::Divide => left.checked_div(right),
+                    Operator::Mod => left.checked_rem(right),
+                    Operator::And | Operator::Ampersand => Some(left & right),
+                    Operator::Or => Some(left | right),
+                    Operator::Xor => Some(left ^ right),
+                    _ => None,
+                }
+            }
+            Self::Bracket(_, inner, _) | Self::Positive(inner) => inner.evaluate_integer(resolve),
+            Self::Negative(inner) => inner.evaluate_integer(resolve)?.checked_neg(),
+            Self::Value(value) => value.integer().or_else(|| match &value.0 {
+                ValueInner::Flat(name) => resolve(name),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
 }
 
+/// Operators bind more tightly than unary `+`, `-` and `NOT` only if their precedence is above this.
+const UNARY_PRECEDENCE: u8 = 8;
+
 impl ExpressionInner {
     fn peel(code: &mut Code) -> Result<Self> {
-        let mut expression = Self::peel_start(code)?;
+        Self::peel_binary(code, 0)
+    }
+
+    fn peel_binary(code: &mut Code, precedence_minimum: u8) -> Result<Self> {
+        let mut expression = Self::peel_unary(code)?;
+
+        loop {
+            let mut code_clone = code.clone();
+            let ethers0 = Ether::peel(&mut code_clone)?;
+            let code_operator = code_clone.clone();
+            let operator = match Operator::peel(&mut code_clone) {
+                Ok(operator) if operator.precedence() >= precedence_minimum => operator,
+                Ok(_) | Err(_) => break,
+            };
+            let spelling = Spelling::between(&code_operator, &code_clone);
+            let ethers1 = Ether::peel(&mut code_clone)?;
+            let right = match Self::peel_binary(&mut code_clone, operator.precedence() + 1) {
+                Ok(right) => right,
+                Err(_) => break,
+            };
+
+            *code = code_clone;
+            expression = Self::BinaryOperator(Box::new(BinaryOperator {
+                left: expression,
+                ethers0,
+                operator,
+                spelling,
+                ethers1,
+                right,
+            }));
+        }
+
+        Ok(expression)
+    }
+
+    fn peel_unary(code: &mut Code) -> Result<Self> {
+        if let Ok(mut code_clone) = code.strip_prefix_keyword("NOT") {
+            let spelling = Spelling::between(code, &code_clone);
+            if let Ok(ethers) = Ether::peel(&mut code_clone) {
+                if let Ok(expression) = Self::peel_binary(&mut code_clone, UNARY_PRECEDENCE + 1) {
+                    *code = code_clone;
+                    return Ok(Self::Not(spelling, ethers, Box::new(expression)));
+                }
+            }
+        }
+
+        if let Ok(mut code_clone) = code.strip_prefix('-') {
+            if let Ok(expression) = Self::peel_binary(&mut code_clone, UNARY_PRECEDENCE + 1) {
+                *code = code_clone;
+                return Ok(Self::Negative(Box::new(expression)));
+            }
+        }
+
+        if let Ok(mut code_clone) = code.strip_prefix('+') {
+            if let Ok(expression) = Self::peel_binary(&mut code_clone, UNARY_PRECEDENCE + 1) {
+                *code = code_clone;
+                return Ok(Self::Positive(Box::new(expression)));
+            }
+        }
+
+        let expression = Self::peel_primary(code)?;
+        Ok(Self::peel_postfix(code, expression))
+    }
+
+    fn peel_primary(code: &mut Code) -> Result<Self> {
+        if let Ok(mut code_clone) = code.strip_prefix('(') {
+            if let Ok(ethers0) = Ether::peel(&mut code_clone) {
+                if let Ok(expression) = Self::peel(&mut code_clone) {
+                    if let Ok(ethers1) = Ether::peel(&mut code_clone) {
+                        if let Ok(code_stripped) = code_clone.strip_prefix(')') {
+                            *code = code_stripped;
+                            return Ok(Self::Bracket(ethers0, Box::new(expression), ethers1));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut code_clone = code.clone();
+        if let Ok(expression) = Value::peel(&mut code_clone) {
+            *code = code_clone;
+            return Ok(Self::Value(expression));
+        }
+
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("No expression\n{code}"),
+        ))
+    }
 
+    fn peel_postfix(code: &mut Code, mut expression: Self) -> Self {
         let mut extended = true;
         while extended {
             extended = false;
 
-            if let Ok(binary_operator) = BinaryOperator::peel_rhs(code) {
-                expression = BinaryOperator::construct(expression, binary_operator);
-                extended = true;
-            }
-
             if let Ok(code_clone) = code.strip_prefix('^') {
                 *code = code_clone;
                 expression = Self::Dereference(Box::new(expression));
@@ -179,9 +305,7 @@ impl ExpressionInner {
                             ethers,
                             arguments,
                         };
-                        expression = expression.extend(method, |left, right| {
-                            Self::Method(Box::new(left), Box::new(right))
-                        });
+                        expression = Self::Method(Box::new(expression), Box::new(method));
                         extended = true;
                     }
                 }
@@ -191,15 +315,13 @@ impl ExpressionInner {
                 code_clone = code_clone.trim_start();
                 if let Ok(field) = Member::peel(&mut code_clone) {
                     *code = code_clone;
-                    expression =
-                        expression.extend(field, |left, right| Self::Field(Box::new(left), right));
+                    expression = Self::Field(Box::new(expression), field);
                     extended = true;
                 }
             }
 
             if let Ok(index) = Self::peel_index(code) {
-                expression =
-                    expression.extend(index, |left, right| Self::Index(Box::new(left), right));
+                expression = Self::Index(Box::new(expression), index);
                 extended = true;
             }
 
@@ -213,49 +335,7 @@ impl ExpressionInner {
             }
         }
 
-        Ok(expression)
-    }
-
-    fn peel_start(code: &mut Code) -> Result<Self> {
-        if let Ok(mut code_clone) = code.strip_prefix_uppercase("NOT") {
-            if let Ok(ethers) = Ether::peel(&mut code_clone) {
-                if let Ok(expression) = Self::peel(&mut code_clone) {
-                    *code = code_clone;
-                    return Ok(Self::Not(ethers, Box::new(expression)));
-                }
-            }
-        }
-
-        if let Ok(mut code_clone) = code.strip_prefix('-') {
-            if let Ok(expression) = Self::peel(&mut code_clone) {
-                *code = code_clone;
-                return Ok(Self::Negative(Box::new(expression)));
-            }
-        }
-
-        if let Ok(mut code_clone) = code.strip_prefix('(') {
-            if let Ok(ethers0) = Ether::peel(&mut code_clone) {
-                if let Ok(expression) = Self::peel(&mut code_clone) {
-                    if let Ok(ethers1) = Ether::peel(&mut code_clone) {
-                        if let Ok(code_stripped) = code_clone.strip_prefix(')') {
-                            *code = code_stripped;
-                            return Ok(Self::Bracket(ethers0, Box::new(expression), ethers1));
-                        }
-                    }
-                }
-            }
-        }
-
-        let mut code_clone = code.clone();
-        if let Ok(expression) = Value::peel(&mut code_clone) {
-            *code = code_clone;
-            return Ok(Self::Value(expression));
-        }
-
-        Err(Error::new(
-            ErrorKind::InvalidData,
-            format!("No expression\n{code}"),
-        ))
+        expression
     }
 
     fn peel_index(code: &mut Code) -> Result<Vec<Self>> {
@@ -269,131 +349,32 @@ impl ExpressionInner {
         *code = code_clone;
         Ok(indices)
     }
-
-    fn extend<T>(self, right: T, outer: impl Fn(Self, T) -> Self) -> Self {
-        match self {
-            Self::BinaryOperator(inner) => Self::BinaryOperator(Box::new(BinaryOperator {
-                left: inner.left,
-                ethers0: inner.ethers0,
-                operator: inner.operator,
-                ethers1: inner.ethers1,
-                right: inner.right.extend(right, outer),
-            })),
-            Self::Negative(inner) => Self::Negative(Box::new(inner.extend(right, outer))),
-            Self::Not(ethers, inner) => Self::Not(ethers, Box::new(inner.extend(right, outer))),
-            Self::Bracket(_, _, _)
-            | Self::Dereference(_)
-            | Self::Field(_, _)
-            | Self::FunctionCall(_)
-            | Self::Index(_, _)
-            | Self::Method(_, _)
-            | Self::Value(_) => outer(self, right),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq)]
-struct BinaryOperator {
-    left: ExpressionInner,
-    ethers0: Vec<Ether>,
-    operator: Operator,
-    ethers1: Vec<Ether>,
-    right: ExpressionInner,
 }
 
-impl BinaryOperator {
-    fn peel_rhs(code: &mut Code) -> Result<(Vec<Ether>, Operator, Vec<Ether>, ExpressionInner)> {
-        let mut code_clone = code.clone();
-
-        let ethers0 = Ether::peel(&mut code_clone)?;
-        let operator = Operator::peel(&mut code_clone)?;
-        let ethers1 = Ether::peel(&mut code_clone)?;
-        let right = ExpressionInner::peel_start(&mut code_clone)?;
-
-        *code = code_clone;
-        Ok((ethers0, operator, ethers1, right))
-    }
-
-    fn construct(
-        lhs: ExpressionInner,
-        (ethers0, operator, ethers1, rhs): (Vec<Ether>, Operator, Vec<Ether>, ExpressionInner),
-    ) -> ExpressionInner {
-        if operator.seperates() {
-            if matches!(lhs, ExpressionInner::BinaryOperator(_)) {
-                let output = ExpressionInner::BinaryOperator(Box::new(Self {
-                    left: lhs,
-                    ethers0,
-                    operator,
-                    ethers1,
-                    right: rhs,
-                }));
-                return output;
-            }
-        } else if let ExpressionInner::BinaryOperator(mut first_binary_operator) = lhs {
-            first_binary_operator.right = ExpressionInner::BinaryOperator(Box::new(Self {
-                left: first_binary_operator.right,
-                ethers0,
-                operator,
-                ethers1,
-                right: rhs,
-            }));
-            let output = ExpressionInner::BinaryOperator(first_binary_operator);
-            return output;
-        }
-
-        if operator.seperates() {
-            if matches!(lhs, ExpressionInner::Not(_, _)) {
-                return ExpressionInner::BinaryOperator(Box::new(Self {
-                    left: lhs,
-                    ethers0,
-                    operator,
-                    ethers1,
-                    right: rhs,
-                }));
-            }
-        } else if let ExpressionInner::Not(ethers, inner) = lhs {
-            return ExpressionInner::Not(
-                ethers,
-                Box::new(ExpressionInner::BinaryOperator(Box::new(Self {
-                    left: *inner,
-                    ethers0,
-                    operator,
-                    ethers1,
-                    right: rhs,
-                }))),
-            );
-        }
-
-        match lhs {
-            ExpressionInner::BinaryOperator(_) => unreachable!(),
-            ExpressionInner::Not(_, _) => unreachable!(),
-            ExpressionInner::Bracket(_, _, _)
-            | ExpressionInner::Dereference(_)
-            | ExpressionInner::Field(_, _)
-            | ExpressionInner::FunctionCall(_)
-            | ExpressionInner::Index(_, _)
-            | ExpressionInner::Method(_, _)
-            | ExpressionInner::Negative(_)
-            | ExpressionInner::Value(_) => ExpressionInner::BinaryOperator(Box::new(Self {
-                left: lhs,
-                ethers0,
-                operator,
-                ethers1,
-                right: rhs,
-            })),
-        }
-    }
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryOperator {
+    pub left: ExpressionInner,
+    pub ethers0: Vec<Ether>,
+    pub operator: Operator,
+    /// How the operator was written, which matters for keywords such as `AND`.
+    pub spelling: Spelling,
+    pub ethers1: Vec<Ether>,
+    pub right: ExpressionInner,
 }
 
-#[derive(Debug, PartialEq)]
-enum Operator {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
     Add,
     Subtract,
     Multiply,
     Divide,
     Mod,
+    Exponent,
     And,
+    Ampersand,
+    AndThen,
     Or,
+    OrElse,
     Xor,
     LessThanOrEqual,
     LessThan,
@@ -405,16 +386,28 @@ enum Operator {
 
 impl Dazzle for Operator {
     fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        self.dazzle_spelled(&Spelling::default(), dazzler);
+    }
+}
+
+impl Operator {
+    /// Writes the operator, spelling keywords such as `AND` as the keyword case policy asks.
+    fn dazzle_spelled(&self, spelling: &Spelling, dazzler: &mut dazzle::Dazzler) {
         dazzler.indent_or_space(true);
+        let case = dazzler.keyword_case;
         match self {
             Self::Add => dazzler.f.push('+'),
             Self::Subtract => dazzler.f.push('-'),
             Self::Multiply => dazzler.f.push('*'),
             Self::Divide => dazzler.f.push('/'),
-            Self::Mod => dazzler.f.push_str("MOD"),
-            Self::And => dazzler.f.push_str("AND"),
-            Self::Or => dazzler.f.push_str("OR"),
-            Self::Xor => dazzler.f.push_str("XOR"),
+            Self::Mod => dazzler.f.push_str(&case.apply("MOD", spelling)),
+            Self::Exponent => dazzler.f.push_str("**"),
+            Self::And => dazzler.f.push_str(&case.apply("AND", spelling)),
+            Self::Ampersand => dazzler.f.push('&'),
+            Self::AndThen => dazzler.f.push_str(&case.apply("AND_THEN", spelling)),
+            Self::Or => dazzler.f.push_str(&case.apply("OR", spelling)),
+            Self::OrElse => dazzler.f.push_str(&case.apply("OR_ELSE", spelling)),
+            Self::Xor => dazzler.f.push_str(&case.apply("XOR", spelling)),
             Self::LessThanOrEqual => dazzler.f.push_str("<="),
             Self::LessThan => dazzler.f.push('<'),
             Self::EqualTo => dazzler.f.push('='),
@@ -424,9 +417,7 @@ impl Dazzle for Operator {
         }
         dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
     }
-}
 
-impl Operator {
     fn peel(code: &mut Code) -> Result<Self> {
         if let Ok(code_clone) = code.strip_prefix('+') {
             *code = code_clone;
@@ -434,22 +425,34 @@ impl Operator {
         } else if let Ok(code_clone) = code.strip_prefix('-') {
             *code = code_clone;
             Ok(Self::Subtract)
+        } else if let Ok(code_clone) = code.strip_prefix_str("**") {
+            *code = code_clone;
+            Ok(Self::Exponent)
         } else if let Ok(code_clone) = code.strip_prefix('*') {
             *code = code_clone;
             Ok(Self::Multiply)
         } else if let Ok(code_clone) = code.strip_prefix('/') {
             *code = code_clone;
             Ok(Self::Divide)
-        } else if let Ok(code_clone) = code.strip_prefix_uppercase("MOD") {
+        } else if let Ok(code_clone) = code.strip_prefix_keyword("MOD") {
             *code = code_clone;
             Ok(Self::Mod)
-        } else if let Ok(code_clone) = code.strip_prefix_uppercase("AND") {
+        } else if let Ok(code_clone) = code.strip_prefix_keyword("AND_THEN") {
+            *code = code_clone;
+            Ok(Self::AndThen)
+        } else if let Ok(code_clone) = code.strip_prefix_keyword("AND") {
             *code = code_clone;
             Ok(Self::And)
-        } else if let Ok(code_clone) = code.strip_prefix_uppercase("OR") {
+        } else if let Ok(code_clone) = code.strip_prefix('&') {
+            *code = code_clone;
+            Ok(Self::Ampersand)
+        } else if let Ok(code_clone) = code.strip_prefix_keyword("OR_ELSE") {
+            *code = code_clone;
+            Ok(Self::OrElse)
+        } else if let Ok(code_clone) = code.strip_prefix_keyword("OR") {
             *code = code_clone;
             Ok(Self::Or)
-        } else if let Ok(code_clone) = code.strip_prefix_uppercase("XOR") {
+        } else if let Ok(code_clone) = code.strip_prefix_keyword("XOR") {
             *code = code_clone;
             Ok(Self::Xor)
         } else if let Ok(code_clone) = code.strip_prefix_str("<=") {
@@ -478,32 +481,39 @@ impl Operator {
         }
     }
 
-    fn seperates(&self) -> bool {
+    /// Binding strength following IEC 61131-3, where a higher value binds more tightly. All binary
+    /// operators associate from left to right.
+    pub fn precedence(&self) -> u8 {
         match self {
-            Self::And | Self::Or | Self::Xor => true,
-            Self::Add
-            | Self::Subtract
-            | Self::Multiply
-            | Self::Divide
-            | Self::Mod
-            | Self::LessThanOrEqual
+            Self::Or | Self::OrElse => 1,
+            Self::Xor => 2,
+            Self::And | Self::Ampersand | Self::AndThen => 3,
+            Self::EqualTo | Self::NotEqualTo => 4,
+            Self::LessThanOrEqual
             | Self::LessThan
-            | Self::EqualTo
-            | Self::NotEqualTo
             | Self::GreaterThan
-            | Self::GreaterThanOrEqual => false,
+            | Self::GreaterThanOrEqual => 5,
+            Self::Add | Self::Subtract => 6,
+            Self::Multiply | Self::Divide | Self::Mod => 7,
+            Self::Exponent => 9,
         }
     }
+
+    /// Whether the right operand is only evaluated if the left operand does not already decide
+    /// the result.
+    pub fn short_circuits(&self) -> bool {
+        matches!(self, Self::AndThen | Self::OrElse)
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FunctionCall {
-    identifier: ExpressionInner,
-    ethers: Vec<Ether>,
-    arguments: Arguments,
+    pub identifier: ExpressionInner,
+    pub ethers: Vec<Ether>,
+    pub arguments: Arguments,
 }
 
-type Arguments = Vec<(Argument, Vec<Ether>)>;
+pub type Arguments = Vec<(Argument, Vec<Ether>)>;
 
 impl Dazzle for FunctionCall {
     fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
@@ -518,95 +528,14 @@ impl Dazzle for FunctionCall {
 fn dazzle_singleline(function_call: &FunctionCall, dazzler: &mut dazzle::Dazzler) {
     dazzler.indent_or_space(false);
     function_call.identifier.dazzle(dazzler);
-    '('.dazzle(dazzler);
-    dazzler.previous_character = dazzle::PreviousCharacter::Other;
-    if !&function_call
-        .ethers
-        .iter()
-        .all(|ether| *ether == Ether::LineFeed)
-    {
-        for ether in &function_call.ethers {
-            ether.dazzle(dazzler);
-        }
-    }
-    for (i, (a, e)) in function_call.arguments.iter().enumerate() {
-        a.dazzle(dazzler);
-        if i + 1 < function_call.arguments.len() {
-            dazzler.f.push(',');
-            dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
-        }
-        if !&e.iter().all(|ether| *ether == Ether::LineFeed) {
-            for ether in e {
-                ether.dazzle(dazzler);
-            }
-        }
-    }
-    ')'.dazzle(dazzler);
+    dazzle_arguments_singleline(&function_call.ethers, &function_call.arguments, dazzler);
 }
 
 impl FunctionCall {
     fn dazzle_multiline(&self, dazzler: &mut dazzle::Dazzler) {
         dazzler.indent_or_space(false);
         self.identifier.dazzle(dazzler);
-        '('.dazzle(dazzler);
-        dazzler.indentation_count += 1;
-        for ether in &self.ethers {
-            ether.dazzle(dazzler);
-        }
-        dazzler.if_not_linefeed_then_linefeed();
-
-        let max_identifier_length = self
-            .arguments
-            .iter()
-            .map(|(argument, _)| match argument {
-                Argument::Unnamed(_) => 0,
-                Argument::InputOrInout(i, _) => i.to_string().len(),
-                Argument::Output(i, _) => i.to_string().len(),
-            })
-            .max()
-            .unwrap_or(0);
-
-        for (argument, ethers) in self.arguments.iter() {
-            match argument {
-                Argument::Unnamed(identifierx) => {
-                    if let Some(identifier) = identifierx {
-                        identifier.dazzle(dazzler);
-                        ','.dazzle(dazzler);
-                    }
-                }
-                Argument::InputOrInout(left, rightx) => {
-                    left.dazzle(dazzler);
-                    for _ in 0..(max_identifier_length - left.to_string().len()) {
-                        dazzler.f.push(' ');
-                    }
-                    dazzler.f.push_str(" :=");
-                    dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
-                    if let Some(right) = rightx {
-                        right.dazzle(dazzler);
-                    }
-                    ','.dazzle(dazzler);
-                }
-                Argument::Output(left, rightx) => {
-                    left.dazzle(dazzler);
-                    for _ in 0..(max_identifier_length - left.to_string().len()) {
-                        dazzler.f.push(' ');
-                    }
-                    dazzler.f.push_str(" =>");
-                    dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
-                    if let Some(right) = rightx {
-                        right.dazzle(dazzler);
-                    }
-                    ','.dazzle(dazzler);
-                }
-            }
-            for ether in ethers {
-                ether.dazzle(dazzler);
-            }
-            dazzler.if_not_linefeed_then_linefeed();
-        }
-
-        dazzler.indentation_count -= 1;
-        ')'.dazzle(dazzler);
+        dazzle_arguments_multiline(&self.ethers, &self.arguments, dazzler);
     }
 
     fn peel_rhs(code: &mut Code) -> Result<(Vec<Ether>, Arguments)> {
@@ -641,8 +570,129 @@ impl FunctionCall {
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Argument {
+/// A parenthesized argument list on its own, such as the `FB_init` arguments of a function block
+/// instance in `fbAxis : FB_Axis(nAxisId := 3);`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArgumentList {
+    pub ethers: Vec<Ether>,
+    pub arguments: Arguments,
+}
+
+impl Dazzle for ArgumentList {
+    fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        if dazzler.should_split(self, dazzle_argument_list_singleline) {
+            dazzle_arguments_multiline(&self.ethers, &self.arguments, dazzler);
+        } else {
+            dazzle_argument_list_singleline(self, dazzler);
+        }
+    }
+}
+
+fn dazzle_argument_list_singleline(argument_list: &ArgumentList, dazzler: &mut dazzle::Dazzler) {
+    dazzle_arguments_singleline(&argument_list.ethers, &argument_list.arguments, dazzler);
+}
+
+impl ArgumentList {
+    pub fn peel(code: &mut Code) -> Result<Self> {
+        let (ethers, arguments) = FunctionCall::peel_rhs(code)?;
+        Ok(Self { ethers, arguments })
+    }
+}
+
+fn dazzle_arguments_singleline(
+    ethers: &[Ether],
+    arguments: &Arguments,
+    dazzler: &mut dazzle::Dazzler,
+) {
+    '('.dazzle(dazzler);
+    dazzler.previous_character = dazzle::PreviousCharacter::Other;
+    if !ethers.iter().all(|ether| *ether == Ether::LineFeed) {
+        for ether in ethers {
+            ether.dazzle(dazzler);
+        }
+    }
+    for (i, (a, e)) in arguments.iter().enumerate() {
+        a.dazzle(dazzler);
+        if i + 1 < arguments.len() {
+            dazzler.f.push(',');
+            dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+        }
+        if !&e.iter().all(|ether| *ether == Ether::LineFeed) {
+            for ether in e {
+                ether.dazzle(dazzler);
+            }
+        }
+    }
+    ')'.dazzle(dazzler);
+}
+
+fn dazzle_arguments_multiline(
+    ethers: &[Ether],
+    arguments: &Arguments,
+    dazzler: &mut dazzle::Dazzler,
+) {
+    '('.dazzle(dazzler);
+    dazzler.indentation_count += 1;
+    for ether in ethers {
+        ether.dazzle(dazzler);
+    }
+    dazzler.if_not_linefeed_then_linefeed();
+
+    let max_identifier_length = arguments
+        .iter()
+        .map(|(argument, _)| match argument {
+            Argument::Unnamed(_) => 0,
+            Argument::InputOrInout(i, _) => dazzle::width(&i.to_string()),
+            Argument::Output(i, _) => dazzle::width(&i.to_string()),
+        })
+        .max()
+        .unwrap_or(0);
+
+    for (argument, ethers) in arguments.iter() {
+        match argument {
+            Argument::Unnamed(identifierx) => {
+                if let Some(identifier) = identifierx {
+                    identifier.dazzle(dazzler);
+                    ','.dazzle(dazzler);
+                }
+            }
+            Argument::InputOrInout(left, rightx) => {
+                left.dazzle(dazzler);
+                for _ in 0..(max_identifier_length - dazzle::width(&left.to_string())) {
+                    dazzler.f.push(' ');
+                }
+                dazzler.f.push_str(" :=");
+                dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+                if let Some(right) = rightx {
+                    right.dazzle(dazzler);
+                }
+                ','.dazzle(dazzler);
+            }
+            Argument::Output(left, rightx) => {
+                left.dazzle(dazzler);
+                for _ in 0..(max_identifier_length - dazzle::width(&left.to_string())) {
+                    dazzler.f.push(' ');
+                }
+                dazzler.f.push_str(" =>");
+                dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+                if let Some(right) = rightx {
+                    right.dazzle(dazzler);
+                }
+                ','.dazzle(dazzler);
+            }
+        }
+        for ether in ethers {
+            ether.dazzle(dazzler);
+        }
+        dazzler.if_not_linefeed_then_linefeed();
+    }
+
+    dazzler.indentation_count -= 1;
+    ')'.dazzle(dazzler);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Argument {
     Unnamed(Option<Expression>),
     InputOrInout(Identifier, Option<Expression>),
     Output(Identifier, Option<Expression>),
@@ -703,19 +753,13 @@ fn peel_identifier_and_expression(
         .trim_start()
         .strip_prefix_str(separator)?
         .trim_start();
-    let expression = match Expression::peel(&mut code_clone) {
-        Ok(exp) => Some(exp),
-        Err(_) => None,
-    };
+    let expression = Expression::peel(&mut code_clone).ok();
     *code = code_clone;
     Ok((identifier, expression))
 }
 
 fn peel_expression_only(code: &mut Code) -> Option<Expression> {
-    match Expression::peel(code) {
-        Ok(expression) => Some(expression),
-        Err(_) => None,
-    }
+    Expression::peel(code).ok()
 }
 
 #[cfg(test)]
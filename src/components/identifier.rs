@@ -2,13 +2,18 @@ use std::fmt;
 use std::io::{Error, ErrorKind, Result};
 
 use crate::code::Code;
+use crate::dazzle::{self, Dazzle};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Identifier(pub String);
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct IdentifierSub(pub String);
 
+/// A comma separated list, such as the interfaces after `IMPLEMENTS`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IdentifierList(pub Vec<IdentifierSub>);
+
 impl fmt::Display for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -35,6 +40,43 @@ impl IdentifierSub {
     }
 }
 
+impl Dazzle for IdentifierList {
+    fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        for (i, identifier) in self.0.iter().enumerate() {
+            if i > 0 {
+                dazzler.f.push(',');
+                let width_current = dazzler.f.lines().last().map_or(0, dazzle::width);
+                let width_comma = usize::from(i + 1 < self.0.len());
+                if width_current + 1 + dazzle::width(&identifier.0) + width_comma
+                    > crate::fmt::LINE_LENGTH_LIMIT
+                {
+                    dazzler.f.push('\n');
+                    dazzler.indentation_count += 1;
+                    dazzler.indent();
+                    dazzler.indentation_count -= 1;
+                } else {
+                    dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+                }
+            }
+            identifier.dazzle(dazzler);
+        }
+    }
+}
+
+impl IdentifierList {
+    pub fn peel(code: &mut Code) -> Result<Self> {
+        let mut code_clone = code.clone();
+        let mut identifiers = vec![IdentifierSub::peel(&mut code_clone)?];
+        while let Ok(mut code_stripped) = code_clone.trim_start().strip_prefix(',') {
+            code_stripped = code_stripped.trim_start();
+            identifiers.push(IdentifierSub::peel(&mut code_stripped)?);
+            code_clone = code_stripped;
+        }
+        *code = code_clone;
+        Ok(Self(identifiers))
+    }
+}
+
 fn peel(code: &mut Code, char_allowed: impl Fn(char) -> bool) -> Result<String> {
     let mut output = String::new();
     for c in code.chars() {
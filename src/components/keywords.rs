@@ -1,6 +1,10 @@
+use crate::code::Code;
+
 pub const KEYWORDS: &[&str] = &[
     "AND",
+    "AND_THEN",
     "OR",
+    "OR_ELSE",
     "XOR",
     "MOD",
     "IF",
@@ -18,4 +22,18 @@ pub const KEYWORDS: &[&str] = &[
     "END_REPEAT",
     "EXIT",
     "RETURN",
+    "CONTINUE",
+    "JMP",
 ];
+
+/// How a keyword was written in the source, such as `End_If` for `END_IF`. Nodes whose keywords
+/// were written differently are not equal, even though keywords are not case sensitive.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Spelling(pub String);
+
+impl Spelling {
+    /// The text peeled from `before` to reach `after`.
+    pub fn between(before: &Code, after: &Code) -> Self {
+        Self(after.since(before.position()).to_string())
+    }
+}
@@ -0,0 +1,457 @@
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+
+use super::Elementary;
+
+/// A literal constant, such as `16#FF`, `1.5E-3`, `TRUE` or `T#1s500ms`, with the spelling it was
+/// written with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Literal {
+    pub kind: LiteralKind,
+    pub spelling: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LiteralKind {
+    Integer {
+        type_prefix: Option<Elementary>,
+        base: u32,
+        value: i128,
+    },
+    Real {
+        type_prefix: Option<Elementary>,
+        value: f64,
+    },
+    Bool(bool),
+    /// A `TIME` or, if `long`, an `LTIME`.
+    Duration {
+        long: bool,
+        nanoseconds: i128,
+    },
+    Date(Date),
+    TimeOfDay(TimeOfDay),
+    DateAndTime(Date, TimeOfDay),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeOfDay {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+/// A `STRING` literal in single quotes or, if `wide`, a `WSTRING` literal in double quotes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StringLiteral {
+    pub wide: bool,
+    /// The characters between the quotes, as written.
+    pub spelling: String,
+    /// The characters the literal stands for, with `$` escapes decoded.
+    pub value: String,
+}
+
+const DURATION_PREFIXES: &[&str] = &["T", "TIME", "LT", "LTIME"];
+const DATE_PREFIXES: &[&str] = &["D", "DATE", "LD", "LDATE"];
+const TIME_OF_DAY_PREFIXES: &[&str] = &["TOD", "TIME_OF_DAY", "LTOD"];
+const DATE_AND_TIME_PREFIXES: &[&str] = &["DT", "DATE_AND_TIME", "LDT"];
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.spelling)
+    }
+}
+
+impl Literal {
+    /// Whether `text` can only be a literal rather than a name. A `#` after anything other than a
+    /// radix, a duration, date or time prefix, or a numeric type is a typed enumeration value such
+    /// as `E_Mode#Auto`.
+    pub fn looks_like(text: &str) -> bool {
+        text.starts_with(|c: char| c.is_ascii_digit())
+            || text
+                .split_once('#')
+                .is_some_and(|(prefix, _)| is_literal_prefix(prefix))
+            || text.eq_ignore_ascii_case("TRUE")
+            || text.eq_ignore_ascii_case("FALSE")
+    }
+
+    /// Whether `separator` after `text` continues a date or time of day, as the `-` and `:` in
+    /// `DT#2024-01-01-12:30`.
+    pub fn continues_with(text: &str, separator: char) -> bool {
+        let prefixes = match separator {
+            '-' => [DATE_PREFIXES, DATE_AND_TIME_PREFIXES].concat(),
+            ':' => [TIME_OF_DAY_PREFIXES, DATE_AND_TIME_PREFIXES].concat(),
+            _ => return false,
+        };
+        match text.split_once('#') {
+            Some((prefix, _)) => prefixes.iter().any(|p| p.eq_ignore_ascii_case(prefix)),
+            None => false,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let kind = LiteralKind::parse(text).map_err(|reason| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Malformed literal `{text}`: {reason}"),
+            )
+        })?;
+        Ok(Self {
+            kind,
+            spelling: text.to_string(),
+        })
+    }
+
+    /// Rewrites the spelling in upper case, covering prefixes, digits, exponents, booleans and
+    /// duration units.
+    pub fn normalize(&mut self) {
+        self.spelling = self.spelling.to_uppercase();
+    }
+}
+
+impl fmt::Display for StringLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let quote = if self.wide { '"' } else { '\'' };
+        write!(f, "{quote}{}{quote}", self.spelling)
+    }
+}
+
+impl StringLiteral {
+    pub fn parse(spelling: &str, wide: bool) -> Result<Self> {
+        let value = decode_escapes(spelling, wide).map_err(|reason| {
+            let quote = if wide { '"' } else { '\'' };
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Malformed string literal {quote}{spelling}{quote}: {reason}"),
+            )
+        })?;
+        Ok(Self {
+            wide,
+            spelling: spelling.to_string(),
+            value,
+        })
+    }
+}
+
+impl LiteralKind {
+    fn parse(text: &str) -> std::result::Result<Self, String> {
+        let Some((prefix, rest)) = text.split_once('#') else {
+            return parse_number(text, None);
+        };
+        let is = |prefixes: &[&str]| prefixes.iter().any(|p| p.eq_ignore_ascii_case(prefix));
+
+        if prefix.chars().all(|c| c.is_ascii_digit()) {
+            parse_number(text, None)
+        } else if is(DURATION_PREFIXES) {
+            let long = prefix.to_uppercase().starts_with('L');
+            Ok(Self::Duration {
+                long,
+                nanoseconds: parse_duration(rest, long)?,
+            })
+        } else if is(DATE_PREFIXES) {
+            Ok(Self::Date(parse_date(rest)?))
+        } else if is(TIME_OF_DAY_PREFIXES) {
+            Ok(Self::TimeOfDay(parse_time_of_day(rest)?))
+        } else if is(DATE_AND_TIME_PREFIXES) {
+            let parts: Vec<&str> = rest.splitn(4, '-').collect();
+            match parts.as_slice() {
+                [year, month, day, time] => Ok(Self::DateAndTime(
+                    parse_date(&format!("{year}-{month}-{day}"))?,
+                    parse_time_of_day(time)?,
+                )),
+                _ => Err(String::from("expected a date and a time of day")),
+            }
+        } else {
+            match Elementary::from_keyword(prefix) {
+                Some(elementary) if is_numeric(elementary) => parse_number(rest, Some(elementary)),
+                Some(_) | None => Err(format!("unknown type prefix `{prefix}`")),
+            }
+        }
+    }
+}
+
+/// Decodes `$$`, `$'`, `$"`, `$L`, `$N`, `$P`, `$R`, `$T` and character codes of two hex digits,
+/// or four in a wide string.
+fn decode_escapes(spelling: &str, wide: bool) -> std::result::Result<String, String> {
+    let mut value = String::new();
+    let mut chars = spelling.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c != '$' {
+            value.push(c);
+            continue;
+        }
+        let at = |reason: String| {
+            format!(
+                "{reason} at character {}",
+                spelling[..i].chars().count() + 1
+            )
+        };
+        let decoded = match chars.next().map(|(_, c)| c) {
+            Some('$') => '$',
+            Some('\'') => '\'',
+            Some('"') => '"',
+            Some('L' | 'l' | 'N' | 'n') => '\n',
+            Some('P' | 'p') => '\x0C',
+            Some('R' | 'r') => '\r',
+            Some('T' | 't') => '\t',
+            Some(first) if first.is_ascii_hexdigit() => {
+                let width = if wide { 4 } else { 2 };
+                let digits: String = std::iter::once(first)
+                    .chain(chars.by_ref().take(width - 1).map(|(_, c)| c))
+                    .collect();
+                if digits.len() < width || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(at(format!(
+                        "character code `${digits}` needs {width} hex digits"
+                    )));
+                }
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| at(format!("`${digits}` is not a character")))?
+            }
+            Some(other) => return Err(at(format!("unknown escape `${other}`"))),
+            None => return Err(at(String::from("unfinished escape `$`"))),
+        };
+        value.push(decoded);
+    }
+    Ok(value)
+}
+
+fn is_literal_prefix(prefix: &str) -> bool {
+    [
+        DURATION_PREFIXES,
+        DATE_PREFIXES,
+        TIME_OF_DAY_PREFIXES,
+        DATE_AND_TIME_PREFIXES,
+    ]
+    .concat()
+    .iter()
+    .any(|p| p.eq_ignore_ascii_case(prefix))
+        || Elementary::from_keyword(prefix).is_some_and(is_numeric)
+}
+
+fn is_numeric(elementary: Elementary) -> bool {
+    matches!(
+        elementary,
+        Elementary::Bool
+            | Elementary::Bit
+            | Elementary::Byte
+            | Elementary::Word
+            | Elementary::DWord
+            | Elementary::LWord
+            | Elementary::SInt
+            | Elementary::Int
+            | Elementary::DInt
+            | Elementary::LInt
+            | Elementary::USInt
+            | Elementary::UInt
+            | Elementary::UDInt
+            | Elementary::ULInt
+            | Elementary::Real
+            | Elementary::LReal
+    )
+}
+
+fn parse_number(
+    text: &str,
+    type_prefix: Option<Elementary>,
+) -> std::result::Result<LiteralKind, String> {
+    if text.eq_ignore_ascii_case("TRUE") || text.eq_ignore_ascii_case("FALSE") {
+        return match type_prefix {
+            None | Some(Elementary::Bool) => Ok(LiteralKind::Bool(text.len() == 4)),
+            Some(_) => Err(String::from("boolean with a numeric type prefix")),
+        };
+    }
+
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let sign = if negative { -1 } else { 1 };
+    if type_prefix == Some(Elementary::Bool) && unsigned.len() != text.len() {
+        return Err(String::from("boolean cannot be signed"));
+    }
+
+    if let Some((base, digits)) = unsigned.split_once('#') {
+        let base = match base {
+            "2" => 2,
+            "8" => 8,
+            "16" => 16,
+            _ => return Err(format!("unsupported base `{base}`")),
+        };
+        let value = parse_digits(digits, base)?;
+        return Ok(LiteralKind::Integer {
+            type_prefix,
+            base,
+            value: sign * value,
+        });
+    }
+
+    let is_real = unsigned.contains(['.', 'e', 'E']);
+    if !is_real {
+        let value = parse_digits(unsigned, 10)?;
+        return match type_prefix {
+            Some(Elementary::Bool) if value > 1 => Err(String::from("boolean must be 0 or 1")),
+            Some(Elementary::Bool) => Ok(LiteralKind::Bool(value == 1)),
+            _ => Ok(LiteralKind::Integer {
+                type_prefix,
+                base: 10,
+                value: sign * value,
+            }),
+        };
+    }
+
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, "0"));
+    parse_digits(whole, 10)?;
+    parse_digits(fraction, 10)?;
+    if let Some(exponent) = exponent {
+        let exponent = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        parse_digits(exponent, 10)?;
+    }
+    match unsigned.replace('_', "").parse::<f64>() {
+        Ok(value) => Ok(LiteralKind::Real {
+            type_prefix,
+            value: if negative { -value } else { value },
+        }),
+        Err(_) => Err(String::from("invalid real number")),
+    }
+}
+
+/// Parses digits in `base`, allowing single underscores between them.
+fn parse_digits(digits: &str, base: u32) -> std::result::Result<i128, String> {
+    if digits.is_empty() {
+        return Err(String::from("missing digits"));
+    }
+    if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+        return Err(String::from("misplaced underscore"));
+    }
+    if let Some(c) = digits.chars().find(|c| *c != '_' && !c.is_digit(base)) {
+        return Err(format!("`{c}` is not a base {base} digit"));
+    }
+    i128::from_str_radix(&digits.replace('_', ""), base).map_err(|_| String::from("too large"))
+}
+
+fn parse_duration(text: &str, long: bool) -> std::result::Result<i128, String> {
+    let (negative, mut rest) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    if rest.is_empty() {
+        return Err(String::from("missing duration"));
+    }
+
+    let mut nanoseconds: i128 = 0;
+    while !rest.is_empty() {
+        rest = rest.strip_prefix('_').unwrap_or(rest);
+        let number_length = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+            .ok_or_else(|| format!("missing unit after `{rest}`"))?;
+        let (number, after) = rest.split_at(number_length);
+        let unit_length = after
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_length);
+
+        let scale: i128 = match unit.to_lowercase().as_str() {
+            "d" => 86_400_000_000_000,
+            "h" => 3_600_000_000_000,
+            "m" => 60_000_000_000,
+            "s" => 1_000_000_000,
+            "ms" => 1_000_000,
+            "us" => 1_000,
+            "ns" => 1,
+            _ => return Err(format!("unknown duration unit `{unit}`")),
+        };
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, "0"));
+        let whole = parse_digits(whole, 10)?;
+        parse_digits(fraction, 10)?;
+        // Digits past the eighteenth are below a nanosecond for every unit.
+        let fraction: String = fraction.chars().filter(|c| *c != '_').take(18).collect();
+        let denominator = 10_i128.pow(fraction.len() as u32);
+        let fraction = parse_digits(&fraction, 10)?;
+        let value = whole
+            .checked_mul(scale)
+            .and_then(|value| value.checked_add((fraction * scale + denominator / 2) / denominator))
+            .and_then(|value| value.checked_add(nanoseconds));
+        nanoseconds = value.ok_or_else(|| String::from("too large"))?;
+        rest = after;
+    }
+
+    let limit = if long {
+        u64::MAX as i128
+    } else {
+        u32::MAX as i128 * 1_000_000
+    };
+    if nanoseconds > limit {
+        return Err(format!(
+            "too large for {}",
+            if long { "LTIME" } else { "TIME" }
+        ));
+    }
+    Ok(if negative { -nanoseconds } else { nanoseconds })
+}
+
+fn parse_date(text: &str) -> std::result::Result<Date, String> {
+    let parts: Vec<&str> = text.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return Err(String::from("expected YYYY-MM-DD"));
+    };
+    let date = Date {
+        year: parse_field(year, u16::MAX)?,
+        month: parse_field(month, 12)?,
+        day: parse_field(day, 31)?,
+    };
+    if date.month == 0 || date.day == 0 {
+        return Err(String::from("months and days start at 1"));
+    }
+    Ok(date)
+}
+
+fn parse_time_of_day(text: &str) -> std::result::Result<TimeOfDay, String> {
+    let parts: Vec<&str> = text.split(':').collect();
+    let (hour, minute, second) = match parts.as_slice() {
+        [hour, minute] => (hour, minute, &"0"),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return Err(String::from("expected HH:MM:SS")),
+    };
+    let (second, fraction) = second.split_once('.').unwrap_or((second, ""));
+    let nanosecond = if fraction.is_empty() {
+        0
+    } else if fraction.len() <= 9 {
+        parse_digits(fraction, 10)? as u32 * 10u32.pow(9 - fraction.len() as u32)
+    } else {
+        return Err(String::from("more than nanosecond precision"));
+    };
+    Ok(TimeOfDay {
+        hour: parse_field(hour, 23)?,
+        minute: parse_field(minute, 59)?,
+        second: parse_field(second, 59)?,
+        nanosecond,
+    })
+}
+
+fn parse_field<T: TryFrom<i128>>(text: &str, max: T) -> std::result::Result<T, String>
+where
+    i128: From<T>,
+{
+    let value = parse_digits(text, 10)?;
+    if value > i128::from(max) {
+        return Err(format!("`{text}` is out of range"));
+    }
+    T::try_from(value).map_err(|_| format!("`{text}` is out of range"))
+}
+
+#[cfg(test)]
+#[path = "./test_literal.rs"]
+mod test_literal;
@@ -6,7 +6,7 @@ use crate::code::Code;
 
 use super::Identifier;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Member {
     Named(Identifier),
     Unnamed(u32),
@@ -1,39 +1,58 @@
 mod address;
-pub use address::Address;
+pub use address::{Address, Area, Location, Size};
 mod expression;
-pub use expression::Expression;
+pub use expression::{
+    Argument, ArgumentList, Arguments, BinaryOperator, Expression, ExpressionInner, FunctionCall,
+    Operator,
+};
 mod begin_middle_end;
 pub use begin_middle_end::BeginMiddleEnd;
+mod case_label;
+pub use case_label::{CaseLabel, CaseLabels};
+mod conditional;
+pub use conditional::{Condition, Conditional, Conditions, Defines};
 mod data_type;
-pub use data_type::DataType;
+pub use data_type::{ArrayRange, DataType, Elementary};
 mod ether;
-pub use ether::Ether;
+pub use ether::{Attribute, Ether};
 mod identifier;
-pub use identifier::{Identifier, IdentifierSub};
+pub use identifier::{Identifier, IdentifierList, IdentifierSub};
 mod keywords;
+pub use keywords::Spelling;
 pub(super) use keywords::KEYWORDS;
+mod literal;
+pub use literal::{Date, Literal, LiteralKind, StringLiteral, TimeOfDay};
 mod member;
-pub(super) use member::Member;
+pub use member::Member;
 mod value;
-pub use value::Value;
+pub use value::{Array, ArrayElement, Struct, Value, ValueInner};
+mod var_section;
+pub use var_section::{VarKind, VarQualifier, VarSection};
 
+use crate::code::Span;
 use crate::dazzle;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Component {
     Ether(Ether),
     Space,
     Address(Address),
+    Arguments(ArgumentList),
+    CaseLabels(CaseLabels),
     DataType(DataType),
     Expression(Expression),
     Identifier(Identifier),
     IdentifierSub(IdentifierSub),
+    IdentifierList(IdentifierList),
     Text(&'static str),
-    Uppercase(&'static str),
+    /// A keyword in upper case, with how it was written.
+    Uppercase(&'static str, Spelling),
     Value(Value),
+    VarSection(VarSection),
     BeginMiddleEnd(BeginMiddleEnd),
     Repeat(Vec<Component>),
-    Filler(u8),
+    Statement(Span, Vec<Component>),
+    Filler(usize),
 }
 
 impl dazzle::Dazzle for Component {
@@ -48,16 +67,20 @@ impl dazzle::Dazzle for Component {
                     arguments.previous_character = dazzle::PreviousCharacter::PendingSpace
                 }
             },
-            Self::Address(inner) => inner.dazzle(arguments),
-            Self::DataType(inner) => inner.dazzle(arguments),
+            Self::Address(inner) => arguments.cased(|f, case| inner.write_cased(f, case)),
+            Self::Arguments(inner) => inner.dazzle(arguments),
+            Self::CaseLabels(inner) => inner.dazzle(arguments),
+            Self::DataType(inner) => arguments.cased(|f, case| inner.write_cased(f, case)),
             Self::Expression(inner) => inner.dazzle(arguments),
             Self::Identifier(inner) => inner.dazzle(arguments),
             Self::IdentifierSub(inner) => inner.dazzle(arguments),
+            Self::IdentifierList(inner) => inner.dazzle(arguments),
             Self::Text(inner) => inner.dazzle(arguments),
-            Self::Uppercase(inner) => inner.dazzle(arguments),
+            Self::Uppercase(inner, spelling) => arguments.keyword(inner, spelling),
             Self::Value(inner) => inner.dazzle(arguments),
+            Self::VarSection(inner) => inner.dazzle(arguments),
             Self::BeginMiddleEnd(inner) => inner.dazzle(arguments),
-            Self::Repeat(inners) => {
+            Self::Repeat(inners) | Self::Statement(_, inners) => {
                 for inner in inners {
                     inner.dazzle(arguments);
                 }
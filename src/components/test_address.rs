@@ -1,7 +1,53 @@
 use super::*;
 
+fn address(input: &str) -> Address {
+    Address::peel(&mut Code::from(input)).unwrap()
+}
+
 #[test]
 fn ok() {
     let mut input = Code::from("AT %Q* : INT;");
-    assert_eq!(Address::Q, Address::peel(&mut input).unwrap());
+    assert_eq!(
+        Address {
+            area: Area::Output,
+            size: None,
+            location: Location::Unspecified,
+            spelling: Spelling(String::from("AT")),
+        },
+        Address::peel(&mut input).unwrap()
+    );
+    assert_eq!(input.to_string(), " : INT;");
+}
+
+#[test]
+fn sizes_and_numbers() {
+    assert_eq!(
+        address("AT %IX0.1 : BOOL;"),
+        Address {
+            area: Area::Input,
+            size: Some(Size::Bit),
+            location: Location::Numbers(vec![0, 1]),
+            spelling: Spelling(String::from("AT")),
+        }
+    );
+    assert_eq!(address("at   %qw10 : WORD;").to_string(), "AT %QW10");
+    assert_eq!(address("AT %MD4 : DWORD;").to_string(), "AT %MD4");
+    assert_eq!(address("AT %IB2.3.4 : BYTE;").to_string(), "AT %IB2.3.4");
+    assert!(Address::peel(&mut Code::from("AT %IX : BOOL;")).is_err());
+}
+
+#[test]
+fn offsets() {
+    assert_eq!(address("AT %IX1.2").bit_offset(), Some(10));
+    assert_eq!(address("AT %MW1").byte_offset(), Some(2));
+    assert_eq!(address("AT %MD1").byte_offset(), Some(4));
+    assert_eq!(address("AT %I*").bit_offset(), None);
+}
+
+#[test]
+fn overlapping() {
+    assert!(address("AT %MW1").overlaps(&address("AT %MX3.7")));
+    assert!(!address("AT %MW1").overlaps(&address("AT %MX4.0")));
+    assert!(address("AT %MD0").overlaps(&address("AT %MB3")));
+    assert!(!address("AT %MD0").overlaps(&address("AT %ID0")));
 }
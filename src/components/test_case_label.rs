@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn list_and_ranges() {
+    let input = String::from("1,2 ,  5 .. 7, E_State.Idle:");
+    let mut code = Code::from(&input);
+
+    let labels = CaseLabels::peel(&mut code).unwrap();
+    assert_eq!(labels.0.len(), 4);
+    assert!(matches!(labels.0[2], CaseLabel::Range(_, _)));
+    assert!(code.to_string().starts_with(':'));
+
+    let mut dazzler = dazzle::Dazzler::default();
+    labels.dazzle(&mut dazzler);
+    assert_eq!(dazzler.f, "1, 2, 5..7, E_State.Idle");
+}
+
+#[test]
+fn negative_range() {
+    let mut code = Code::from("-10..-1:");
+
+    let mut dazzler = dazzle::Dazzler::default();
+    CaseLabels::peel(&mut code).unwrap().dazzle(&mut dazzler);
+    assert_eq!(dazzler.f, "-10..-1");
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+fn defines(names: &[(&str, &str)]) -> Defines {
+    names
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Conditional::parse("IF defined (pou: MC_Power)"),
+        Some(Conditional::If(Condition::Defined(String::from(
+            "pou:MC_Power"
+        ))))
+    );
+    assert_eq!(Conditional::parse("else"), Some(Conditional::Else));
+    assert_eq!(Conditional::parse("END_IF"), Some(Conditional::EndIf));
+    assert_eq!(Conditional::parse("attribute 'hide'"), None);
+    assert_eq!(Conditional::parse("IF"), None);
+
+    for (input, output) in [
+        ("IF defined(SIMULATION)", "IF defined (SIMULATION)"),
+        (
+            "ELSIF NOT defined (A) AND (hasvalue (B, '2') OR defined (C))",
+            "ELSIF NOT defined (A) AND (hasvalue (B, '2') OR defined (C))",
+        ),
+        (
+            "IF hasattribute (pou: FB_X, 'monitoring')",
+            "IF hasattribute (pou: FB_X, 'monitoring')",
+        ),
+        ("IF defined (A) AND", "IF defined (A) AND"),
+    ] {
+        assert_eq!(Conditional::parse(input).unwrap().to_string(), output);
+    }
+}
+
+#[test]
+fn evaluate() {
+    let defines = defines(&[("SIMULATION", ""), ("pou:MC_Power", ""), ("AXES", "2")]);
+    for (condition, expected) in [
+        ("defined (simulation)", true),
+        ("defined (pou:MC_Power)", true),
+        ("defined (REAL_AXES)", false),
+        ("NOT defined (REAL_AXES)", true),
+        ("hasvalue (AXES, '2') AND defined (SIMULATION)", true),
+        ("hasvalue (AXES, '3') OR defined (REAL_AXES)", false),
+        ("hasattribute (pou: FB_X, 'monitoring')", false),
+    ] {
+        assert_eq!(Condition::parse(condition).evaluate(&defines), expected);
+    }
+}
+
+#[test]
+fn conditions() {
+    let defines = defines(&[("B", "")]);
+    let mut conditions = Conditions::new(&defines);
+    let mut active = Vec::new();
+    for pragma in [
+        "IF defined (A)",
+        "ELSIF defined (B)",
+        "IF defined (C)",
+        "ELSE",
+        "END_IF",
+        "ELSE",
+        "END_IF",
+    ] {
+        conditions.update(&Conditional::parse(pragma).unwrap());
+        active.push(conditions.is_active());
+    }
+    assert_eq!(active, [false, true, false, true, true, false, true]);
+}
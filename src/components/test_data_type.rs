@@ -1,5 +1,17 @@
 use super::*;
 
+fn expression(input: &str) -> Expression {
+    Expression::peel(&mut Code::from(input)).unwrap()
+}
+
+fn range(lower: &str, upper: &str) -> ArrayRange {
+    ArrayRange::LowerUpper(expression(lower), expression(upper))
+}
+
+fn spelling(text: &str) -> Spelling {
+    Spelling(text.to_string())
+}
+
 #[test]
 fn simple() {
     let mut input = Code::from("xyz;");
@@ -15,8 +27,9 @@ fn array() {
     assert_eq!(
         DataType::peel(&mut input).unwrap(),
         DataType::Array(
-            ArrayRange::LowerUpper(String::from("2"), String::from("8")),
-            Box::new(DataType::Flat(String::from("xyz")))
+            vec![range("2", "8")],
+            Box::new(DataType::Flat(String::from("xyz"))),
+            vec![spelling("ARRAY"), spelling("OF")]
         ),
     );
 }
@@ -27,11 +40,13 @@ fn array_of_array() {
     assert_eq!(
         DataType::peel(&mut input).unwrap(),
         DataType::Array(
-            ArrayRange::LowerUpper(String::from("-2"), String::from("12")),
+            vec![range("-2", "12")],
             Box::new(DataType::Array(
-                ArrayRange::LowerUpper(String::from("3"), String::from("8")),
-                Box::new(DataType::Flat(String::from("xyz")))
-            ))
+                vec![range("3", "8")],
+                Box::new(DataType::Flat(String::from("xyz"))),
+                vec![spelling("ARRAY"), spelling("OF")]
+            )),
+            vec![spelling("ARRAY"), spelling("OF")]
         ),
     );
 }
@@ -48,8 +63,9 @@ fn array_messy() {
     assert_eq!(
         DataType::peel(&mut input).unwrap(),
         DataType::Array(
-            ArrayRange::LowerUpper(String::from("3"), String::from("LOTS")),
-            Box::new(DataType::Flat(String::from("xyz")))
+            vec![range("3", "LOTS")],
+            Box::new(DataType::Flat(String::from("xyz"))),
+            vec![spelling("array"), spelling("Of")]
         ),
     );
 }
@@ -60,8 +76,9 @@ fn array_of_star_length() {
     assert_eq!(
         DataType::peel(&mut input).unwrap(),
         DataType::Array(
-            ArrayRange::Star,
-            Box::new(DataType::Flat(String::from("UINT")))
+            vec![ArrayRange::Star],
+            Box::new(DataType::Elementary(Elementary::UInt, spelling("UINT"))),
+            vec![spelling("ARRAY"), spelling("OF")]
         ),
     );
 }
@@ -69,7 +86,10 @@ fn array_of_star_length() {
 #[test]
 fn string() {
     let mut input = Code::from("STRING := 'hello';");
-    assert_eq!(DataType::peel(&mut input).unwrap(), DataType::String(None));
+    assert_eq!(
+        DataType::peel(&mut input).unwrap(),
+        DataType::String(None, spelling("STRING"))
+    );
 }
 
 #[test]
@@ -77,6 +97,115 @@ fn string_with_length() {
     let mut input = Code::from("STRING( 248 ) := 'hello';");
     assert_eq!(
         DataType::peel(&mut input).unwrap(),
-        DataType::String(Some(248)),
+        DataType::String(Some(expression("248")), spelling("STRING")),
+    );
+}
+
+#[test]
+fn subrange() {
+    let mut input = Code::from("INT (-5 .. 10);");
+    let data_type = DataType::peel(&mut input).unwrap();
+
+    assert_eq!(
+        data_type,
+        DataType::Subrange(
+            Box::new(DataType::Elementary(Elementary::Int, spelling("INT"))),
+            expression("-5"),
+            expression("10")
+        ),
+    );
+    assert_eq!(data_type.to_string(), "INT(-5..10)");
+    assert_eq!(input.to_string(), ";");
+}
+
+#[test]
+fn array_multi_dimensional() {
+    let mut input = Code::from("ARRAY[0..1,0 .. 2] OF INT;");
+    let data_type = DataType::peel(&mut input).unwrap();
+
+    assert_eq!(data_type.dimensions().unwrap().len(), 2);
+    assert_eq!(data_type.to_string(), "ARRAY [0..1, 0..2] OF INT");
+
+    let mut input = Code::from("ARRAY [*, *] OF REAL;");
+    let data_type = DataType::peel(&mut input).unwrap();
+
+    assert_eq!(
+        data_type.dimensions().unwrap(),
+        &[ArrayRange::Star, ArrayRange::Star]
+    );
+    assert_eq!(data_type.to_string(), "ARRAY [*, *] OF REAL");
+}
+
+#[test]
+fn array_expression_bounds() {
+    let mut input = Code::from("ARRAY[0..GVL_Const.MAX_AXES-1] OF FB_Axis;");
+    let data_type = DataType::peel(&mut input).unwrap();
+    let dimension = &data_type.dimensions().unwrap()[0];
+
+    assert_eq!(dimension.lower(), Some(&expression("0")));
+    assert_eq!(
+        dimension.upper(),
+        Some(&expression("GVL_Const.MAX_AXES - 1"))
+    );
+    assert_eq!(
+        data_type.to_string(),
+        "ARRAY [0..GVL_Const.MAX_AXES - 1] OF FB_Axis"
+    );
+    assert_eq!(input.to_string(), ";");
+}
+
+#[test]
+fn strings() {
+    let mut input = Code::from("WSTRING(255) := \"hello\";");
+    let data_type = DataType::peel(&mut input).unwrap();
+    assert_eq!(
+        data_type,
+        DataType::WString(Some(expression("255")), spelling("WSTRING"))
+    );
+    assert_eq!(input.to_string(), " := \"hello\";");
+
+    for (input, output) in [
+        ("STRING(GVL.MAX_LEN)", "STRING(GVL.MAX_LEN)"),
+        ("STRING [80]", "STRING(80)"),
+        ("wstring", "WSTRING"),
+        ("STRING(2 * N + 1)", "STRING(2 * N + 1)"),
+    ] {
+        assert_eq!(
+            DataType::peel(&mut Code::from(input)).unwrap().to_string(),
+            output
+        );
+    }
+}
+
+#[test]
+fn elementary() {
+    for (input, elementary) in [
+        ("BOOL;", Elementary::Bool),
+        ("lreal;", Elementary::LReal),
+        ("TIME_OF_DAY;", Elementary::TimeOfDay),
+        ("__XWORD;", Elementary::XWord),
+        ("ANY_NUM;", Elementary::AnyNum),
+    ] {
+        assert_eq!(
+            DataType::peel(&mut Code::from(input)).unwrap(),
+            DataType::Elementary(
+                elementary,
+                Spelling(input.trim_end_matches(';').to_string())
+            )
+        );
+    }
+    assert_eq!(
+        DataType::peel(&mut Code::from("time_of_day"))
+            .unwrap()
+            .to_string(),
+        "TIME_OF_DAY"
+    );
+    assert_eq!(
+        DataType::Elementary(Elementary::DateAndTime, Spelling::default()).to_string(),
+        "DT"
+    );
+    assert_eq!(
+        DataType::peel(&mut Code::from("INTERFACE_X;")).unwrap(),
+        DataType::Flat(String::from("INTERFACE_X"))
     );
 }
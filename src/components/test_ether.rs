@@ -30,3 +30,48 @@ fn nested_comment() {
 
     assert!(input.end_of_file());
 }
+
+#[test]
+fn attributes() {
+    for (input, name, value, output) in [
+        ("{attribute 'hide'}", "hide", None, "{attribute 'hide'}"),
+        (
+            "{attribute 'pack_mode':='1'}",
+            "pack_mode",
+            Some("1"),
+            "{attribute 'pack_mode' := '1'}",
+        ),
+        (
+            "{ attribute 'TcLinkTo' := 'TIIB[EL1008]^Channel 1^Input' }",
+            "TcLinkTo",
+            Some("TIIB[EL1008]^Channel 1^Input"),
+            "{attribute 'TcLinkTo' := 'TIIB[EL1008]^Channel 1^Input'}",
+        ),
+        (
+            "{Attribute 'TcLinkTo' := 'x'}",
+            "TcLinkTo",
+            Some("x"),
+            "{attribute 'TcLinkTo' := 'x'}",
+        ),
+    ] {
+        let ethers = Ether::peel(&mut Code::from(input)).unwrap();
+        let attribute = ethers[0].attribute().unwrap();
+        assert_eq!(attribute.name, name);
+        assert_eq!(attribute.value.as_deref(), value);
+
+        let mut dazzler = dazzle::Dazzler::default();
+        ethers[0].dazzle(&mut dazzler);
+        assert_eq!(dazzler.f, output);
+    }
+
+    let ethers = Ether::peel(&mut Code::from("{warning 'attribute'}")).unwrap();
+    assert!(ethers[0].attribute().is_none());
+    assert_eq!(
+        Attribute::parse(" attribute 'hide'"),
+        Some(Attribute {
+            name: String::from("hide"),
+            value: None
+        })
+    );
+    assert_eq!(Attribute::parse("attributes 'hide'"), None);
+}
@@ -54,68 +54,131 @@ fn not_x_and_not_y() {
     assert_eq!(dazzler.f, output_string);
 }
 
+fn value(text: &str) -> ExpressionInner {
+    ExpressionInner::Value(Value::peel(&mut Code::from(text)).unwrap())
+}
+
+/// A binary operation, with the operator spelled as the formatter writes it.
+fn binary(left: ExpressionInner, operator: Operator, right: ExpressionInner) -> ExpressionInner {
+    let mut dazzler = dazzle::Dazzler::default();
+    operator.dazzle(&mut dazzler);
+    ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
+        left,
+        ethers0: vec![],
+        operator,
+        spelling: Spelling(dazzler.f.trim().to_string()),
+        ethers1: vec![],
+        right,
+    }))
+}
+
 #[test]
-fn condition_left_to_right() {
+fn condition_precedence() {
     let input = String::from("a + b AND c - d OR e OR g <> h AND q - 3 < r");
     let mut code = Code::from(&input);
 
-    let output = Expression(ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-        left: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-            left: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-                left: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-                    left: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-                        left: ExpressionInner::Value(Value::peel(&mut Code::from("a")).unwrap()),
-                        ethers0: vec![],
-                        operator: Operator::Add,
-                        ethers1: vec![],
-                        right: ExpressionInner::Value(Value::peel(&mut Code::from("b")).unwrap()),
-                    })),
-                    ethers0: vec![],
-                    operator: Operator::And,
-                    ethers1: vec![],
-                    right: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-                        left: ExpressionInner::Value(Value::peel(&mut Code::from("c")).unwrap()),
-                        ethers0: vec![],
-                        operator: Operator::Subtract,
-                        ethers1: vec![],
-                        right: ExpressionInner::Value(Value::peel(&mut Code::from("d")).unwrap()),
-                    })),
-                })),
-                ethers0: vec![],
-                operator: Operator::Or,
-                ethers1: vec![],
-                right: ExpressionInner::Value(Value::peel(&mut Code::from("e")).unwrap()),
-            })),
-            ethers0: vec![],
-            operator: Operator::Or,
-            ethers1: vec![],
-            right: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-                left: ExpressionInner::Value(Value::peel(&mut Code::from("g")).unwrap()),
-                ethers0: vec![],
-                operator: Operator::NotEqualTo,
-                ethers1: vec![],
-                right: ExpressionInner::Value(Value::peel(&mut Code::from("h")).unwrap()),
-            })),
-        })),
-        ethers0: vec![],
-        operator: Operator::And,
-        ethers1: vec![],
-        right: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-            left: ExpressionInner::BinaryOperator(Box::new(BinaryOperator {
-                left: ExpressionInner::Value(Value::peel(&mut Code::from("q")).unwrap()),
-                ethers0: vec![],
-                operator: Operator::Subtract,
-                ethers1: vec![],
-                right: ExpressionInner::Value(Value::peel(&mut Code::from("3")).unwrap()),
-            })),
-            ethers0: vec![],
-            operator: Operator::LessThan,
-            ethers1: vec![],
-            right: ExpressionInner::Value(Value::peel(&mut Code::from("r")).unwrap()),
-        })),
-    })));
+    let output = Expression(binary(
+        binary(
+            binary(
+                binary(value("a"), Operator::Add, value("b")),
+                Operator::And,
+                binary(value("c"), Operator::Subtract, value("d")),
+            ),
+            Operator::Or,
+            value("e"),
+        ),
+        Operator::Or,
+        binary(
+            binary(value("g"), Operator::NotEqualTo, value("h")),
+            Operator::And,
+            binary(
+                binary(value("q"), Operator::Subtract, value("3")),
+                Operator::LessThan,
+                value("r"),
+            ),
+        ),
+    ));
 
     assert_eq!(Expression::peel(&mut code).unwrap(), output);
+    assert!(code.end_of_file());
+}
+
+#[test]
+fn arithmetic_precedence() {
+    let mut code = Code::from("a + b * c - d / e MOD f");
+
+    let output = Expression(binary(
+        binary(
+            value("a"),
+            Operator::Add,
+            binary(value("b"), Operator::Multiply, value("c")),
+        ),
+        Operator::Subtract,
+        binary(
+            binary(value("d"), Operator::Divide, value("e")),
+            Operator::Mod,
+            value("f"),
+        ),
+    ));
+
+    assert_eq!(Expression::peel(&mut code).unwrap(), output);
+}
+
+#[test]
+fn comparison_precedence() {
+    let mut code = Code::from("a = b + c <> d < e");
+
+    let output = Expression(binary(
+        binary(
+            value("a"),
+            Operator::EqualTo,
+            binary(value("b"), Operator::Add, value("c")),
+        ),
+        Operator::NotEqualTo,
+        binary(value("d"), Operator::LessThan, value("e")),
+    ));
+
+    assert_eq!(Expression::peel(&mut code).unwrap(), output);
+}
+
+#[test]
+fn unary_precedence() {
+    let mut code = Code::from("NOT a = -b * c XOR d");
+
+    let output = Expression(binary(
+        binary(
+            ExpressionInner::Not(Spelling(String::from("NOT")), vec![], Box::new(value("a"))),
+            Operator::EqualTo,
+            binary(
+                ExpressionInner::Negative(Box::new(value("b"))),
+                Operator::Multiply,
+                value("c"),
+            ),
+        ),
+        Operator::Xor,
+        value("d"),
+    ));
+
+    assert_eq!(Expression::peel(&mut code).unwrap(), output);
+}
+
+#[test]
+fn comments_stay_attached() {
+    let input = String::from("a // first\n    + b * (* second *) c");
+    let mut code = Code::from(&input);
+
+    let expression = Expression::peel(&mut code).unwrap();
+    assert!(code.end_of_file());
+    match expression.0 {
+        ExpressionInner::BinaryOperator(outer) => {
+            assert!(outer.ethers0[0].is_comment());
+            match outer.right {
+                ExpressionInner::BinaryOperator(inner) => assert!(inner.ethers1[0].is_comment()),
+                right => panic!("{right:?}"),
+            }
+        }
+        expression => panic!("{expression:?}"),
+    }
 }
 
 #[test]
@@ -202,3 +265,39 @@ fn function_call_short() {
         .dazzle(&mut dazzler);
     assert_eq!(dazzler.f, output);
 }
+
+#[test]
+fn extra_operators() {
+    let input = String::from("+a ** 2 & b OR_ELSE c AND_THEN NOT d");
+    let mut code = Code::from(&input);
+
+    let expression = Expression::peel(&mut code).unwrap();
+    assert!(code.end_of_file());
+
+    let output = Expression(binary(
+        binary(
+            ExpressionInner::Positive(Box::new(binary(value("a"), Operator::Exponent, value("2")))),
+            Operator::Ampersand,
+            value("b"),
+        ),
+        Operator::OrElse,
+        binary(
+            value("c"),
+            Operator::AndThen,
+            ExpressionInner::Not(Spelling(String::from("NOT")), vec![], Box::new(value("d"))),
+        ),
+    ));
+    assert_eq!(expression, output);
+
+    let mut dazzler = dazzle::Dazzler::default();
+    expression.dazzle(&mut dazzler);
+    assert_eq!(dazzler.f, input);
+}
+
+#[test]
+fn keyword_prefixed_names() {
+    let mut code = Code::from("NOTHING ANDY ORDER");
+    let mut dazzler = dazzle::Dazzler::default();
+    Expression::peel(&mut code).unwrap().dazzle(&mut dazzler);
+    assert_eq!(dazzler.f, "NOTHING");
+}
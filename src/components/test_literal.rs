@@ -0,0 +1,221 @@
+use super::*;
+
+fn kind(text: &str) -> LiteralKind {
+    Literal::parse(text).unwrap().kind
+}
+
+#[test]
+fn integers() {
+    assert_eq!(
+        kind("16#FF_FF"),
+        LiteralKind::Integer {
+            type_prefix: None,
+            base: 16,
+            value: 0xFFFF,
+        }
+    );
+    assert_eq!(
+        kind("2#1010"),
+        LiteralKind::Integer {
+            type_prefix: None,
+            base: 2,
+            value: 10,
+        }
+    );
+    assert_eq!(
+        kind("INT#-5"),
+        LiteralKind::Integer {
+            type_prefix: Some(Elementary::Int),
+            base: 10,
+            value: -5,
+        }
+    );
+    assert_eq!(
+        kind("UDINT#16#1_0"),
+        LiteralKind::Integer {
+            type_prefix: Some(Elementary::UDInt),
+            base: 16,
+            value: 16,
+        }
+    );
+}
+
+#[test]
+fn reals_and_booleans() {
+    assert_eq!(
+        kind("1.5E-3"),
+        LiteralKind::Real {
+            type_prefix: None,
+            value: 1.5E-3,
+        }
+    );
+    assert_eq!(
+        kind("REAL#2.0"),
+        LiteralKind::Real {
+            type_prefix: Some(Elementary::Real),
+            value: 2.0,
+        }
+    );
+    assert_eq!(kind("TRUE"), LiteralKind::Bool(true));
+    assert_eq!(kind("false"), LiteralKind::Bool(false));
+    assert_eq!(kind("BOOL#1"), LiteralKind::Bool(true));
+}
+
+#[test]
+fn durations() {
+    assert_eq!(
+        kind("T#1s500ms"),
+        LiteralKind::Duration {
+            long: false,
+            nanoseconds: 1_500_000_000,
+        }
+    );
+    assert_eq!(
+        kind("LTIME#1h_30m"),
+        LiteralKind::Duration {
+            long: true,
+            nanoseconds: 5_400_000_000_000,
+        }
+    );
+    assert_eq!(
+        kind("t#-2.5S"),
+        LiteralKind::Duration {
+            long: false,
+            nanoseconds: -2_500_000_000,
+        }
+    );
+    assert_eq!(
+        kind("LTIME#106751d23h47m16s854ms775us807ns"),
+        LiteralKind::Duration {
+            long: true,
+            nanoseconds: i64::MAX as i128,
+        }
+    );
+    assert_eq!(
+        kind("LTIME#0.333333333333333333333s"),
+        LiteralKind::Duration {
+            long: true,
+            nanoseconds: 333_333_333,
+        }
+    );
+}
+
+#[test]
+fn dates_and_times() {
+    let date = Date {
+        year: 2024,
+        month: 1,
+        day: 1,
+    };
+    let time = TimeOfDay {
+        hour: 12,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+    };
+    assert_eq!(kind("D#2024-01-01"), LiteralKind::Date(date));
+    assert_eq!(
+        kind("DT#2024-01-01-12:00"),
+        LiteralKind::DateAndTime(date, time)
+    );
+    assert_eq!(
+        kind("TOD#23:59:59.5"),
+        LiteralKind::TimeOfDay(TimeOfDay {
+            hour: 23,
+            minute: 59,
+            second: 59,
+            nanosecond: 500_000_000,
+        })
+    );
+}
+
+#[test]
+fn malformed() {
+    for (text, reason) in [
+        ("T#1x", "unknown duration unit `x`"),
+        ("16#GG", "`G` is not a base 16 digit"),
+        ("1__0", "misplaced underscore"),
+        ("D#2024-13-01", "`13` is out of range"),
+        ("FOO#1", "unknown type prefix `FOO`"),
+        ("3#12", "unsupported base `3`"),
+        ("BOOL#-1", "boolean cannot be signed"),
+        ("BOOL#+0", "boolean cannot be signed"),
+        ("LTIME#99999999999999999d", "too large for LTIME"),
+        ("T#50d", "too large for TIME"),
+    ] {
+        let error = Literal::parse(text).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Malformed literal `{text}`: {reason}")
+        );
+    }
+}
+
+#[test]
+fn normalize() {
+    for (text, normalized) in [
+        ("t#1S", "T#1S"),
+        ("T#1s500ms", "T#1S500MS"),
+        ("ltime#1h_30m", "LTIME#1H_30M"),
+        ("16#ff", "16#FF"),
+        ("true", "TRUE"),
+        ("1.5e-3", "1.5E-3"),
+        ("dt#2024-01-01-12:00", "DT#2024-01-01-12:00"),
+    ] {
+        let mut literal = Literal::parse(text).unwrap();
+        literal.normalize();
+        assert_eq!(literal.spelling, normalized);
+    }
+}
+
+#[test]
+fn string_escapes() {
+    for (spelling, wide, value) in [
+        ("line$0D$0A", false, "line\r\n"),
+        ("$R$N$L$T$P", false, "\r\n\n\t\x0C"),
+        ("it$'s $$5", false, "it's $5"),
+        ("$\"quoted$\"", true, "\"quoted\""),
+        ("$00C4rger", true, "Ärger"),
+        ("plain", false, "plain"),
+    ] {
+        assert_eq!(StringLiteral::parse(spelling, wide).unwrap().value, value);
+    }
+}
+
+#[test]
+fn string_malformed() {
+    for (spelling, wide, reason) in [
+        ("$q", false, "unknown escape `$q` at character 1"),
+        ("trailing $", false, "unfinished escape `$` at character 10"),
+        (
+            "$0",
+            false,
+            "character code `$0` needs 2 hex digits at character 1",
+        ),
+        (
+            "$0DZZ",
+            true,
+            "character code `$0DZZ` needs 4 hex digits at character 1",
+        ),
+        ("$D800", true, "`$D800` is not a character at character 1"),
+    ] {
+        let error = StringLiteral::parse(spelling, wide).unwrap_err();
+        let quote = if wide { '"' } else { '\'' };
+        assert_eq!(
+            error.to_string(),
+            format!("Malformed string literal {quote}{spelling}{quote}: {reason}")
+        );
+    }
+}
+
+#[test]
+fn looks_like() {
+    assert!(Literal::looks_like("16#FF"));
+    assert!(Literal::looks_like("t#1s"));
+    assert!(Literal::looks_like("DT#2024-01-01-12:30"));
+    assert!(Literal::looks_like("WORD#2#1010"));
+    assert!(Literal::looks_like("true"));
+    assert!(!Literal::looks_like("E_Mode#Auto"));
+    assert!(!Literal::looks_like("STRING#abc"));
+    assert!(!Literal::looks_like("nValue"));
+}
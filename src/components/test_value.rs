@@ -1,4 +1,9 @@
 use super::*;
+use crate::components::ExpressionInner;
+
+fn single(input: &str) -> ArrayElement {
+    ArrayElement::Single(Expression::peel(&mut Code::from(input)).unwrap())
+}
 
 #[test]
 fn array() {
@@ -8,13 +13,10 @@ fn array() {
         Value(ValueInner::Array(Array(
             vec![],
             vec![
-                (Expression::peel(&mut Code::from("0")).unwrap(), vec![]),
-                (Expression::peel(&mut Code::from("4")).unwrap(), vec![]),
-                (
-                    Expression::peel(&mut Code::from("SOME_CONSTANT")).unwrap(),
-                    vec![]
-                ),
-                (Expression::peel(&mut Code::from("-2")).unwrap(), vec![]),
+                (single("0"), vec![]),
+                (single("4"), vec![]),
+                (single("SOME_CONSTANT"), vec![]),
+                (single("-2"), vec![]),
             ]
         ))),
     );
@@ -28,14 +30,11 @@ fn array_and_array_accessor() {
         Value(ValueInner::Array(Array(
             vec![],
             vec![
-                (Expression::peel(&mut Code::from("0")).unwrap(), vec![]),
-                (Expression::peel(&mut Code::from("4")).unwrap(), vec![]),
-                (
-                    Expression::peel(&mut Code::from("SOME_ARRAY[pp]")).unwrap(),
-                    vec![]
-                ),
-                (Expression::peel(&mut Code::from("-2")).unwrap(), vec![]),
-                (Expression::peel(&mut Code::from("x[3]")).unwrap(), vec![]),
+                (single("0"), vec![]),
+                (single("4"), vec![]),
+                (single("SOME_ARRAY[pp]"), vec![]),
+                (single("-2"), vec![]),
+                (single("x[3]"), vec![]),
             ]
         ))),
     );
@@ -136,27 +135,133 @@ fn structure_very_long() {
 fn string() {
     let mut input = Code::from("'Trees!';");
     assert_eq!(
-        Value(ValueInner::String(String::from("Trees!"))),
+        Value(ValueInner::String(
+            StringLiteral::parse("Trees!", false).unwrap()
+        )),
         Value::peel(&mut input).unwrap(),
     );
 }
 
 #[test]
 fn string_with_escapes() {
-    let mut input = Code::from("'Let$'s escape! We shall be $$free$$ like the wind in the trees.'");
+    let input_output = "'Let$'s escape! We shall be $$free$$ like the wind in the trees.'";
+    let value = Value::peel(&mut Code::from(input_output)).unwrap();
     assert_eq!(
-        Value(ValueInner::String(String::from(
-            "Let$'s escape! We shall be $$free$$ like the wind in the trees."
-        ))),
-        Value::peel(&mut input).unwrap(),
+        value.text(),
+        Some("Let's escape! We shall be $free$ like the wind in the trees.")
+    );
+
+    let mut dazzler = dazzle::Dazzler::default();
+    value.dazzle(&mut dazzler);
+    assert_eq!(dazzler.f, input_output);
+}
+
+#[test]
+fn string_unknown_escape() {
+    let error = Value::peel(&mut Code::from("'50$%';")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Malformed string literal '50$%': unknown escape `$%` at character 3"
     );
 }
 
 #[test]
 fn flat() {
+    let mut input = Code::from("E_State.Idle;");
+    assert_eq!(
+        Value(ValueInner::Flat(String::from("E_State.Idle"))),
+        Value::peel(&mut input).unwrap(),
+    );
+}
+
+#[test]
+fn literal() {
     let mut input = Code::from("3.14;");
     assert_eq!(
-        Value(ValueInner::Flat(String::from("3.14"))),
+        Value(ValueInner::Literal(Literal::parse("3.14").unwrap())),
         Value::peel(&mut input).unwrap(),
     );
+
+    let mut input = Code::from("DT#2024-01-01-12:00 : x := 1;");
+    Value::peel(&mut input).unwrap();
+    assert_eq!(input.to_string(), " : x := 1;");
+
+    let mut input = Code::from("16#FF: x := 1;");
+    Value::peel(&mut input).unwrap();
+    assert_eq!(input.to_string(), ": x := 1;");
+
+    assert!(Value::peel(&mut Code::from("T#1x;")).is_err());
+}
+
+#[test]
+fn wstring_literal() {
+    let mut input = Code::from("\"wide $\" text\";");
+    let value = Value::peel(&mut input).unwrap();
+
+    assert_eq!(
+        value,
+        Value(ValueInner::String(
+            StringLiteral::parse("wide $\" text", true).unwrap()
+        ))
+    );
+    assert_eq!(value.text(), Some("wide \" text"));
+    assert_eq!(input.to_string(), ";");
+}
+
+#[test]
+fn literal_then_operator() {
+    for (input, rest) in [
+        ("16#1E-5;", "-5;"),
+        ("REAL#1.5E-3-x;", "-x;"),
+        ("INT#-5+1;", "+1;"),
+        ("D#2024-01-01-x;", "-x;"),
+    ] {
+        let mut code = Code::from(input);
+        Value::peel(&mut code).unwrap();
+        assert_eq!(code.to_string(), rest);
+    }
+}
+
+#[test]
+fn array_repetition() {
+    let mut input = Code::from("[10(0), 5(SOME_CONSTANT), 2(), 16#F(1)]");
+    let Value(ValueInner::Array(array)) = Value::peel(&mut input).unwrap() else {
+        panic!("Expected an array");
+    };
+    let elements: Vec<&ArrayElement> = array.1.iter().map(|(element, _)| element).collect();
+    assert_eq!(
+        elements,
+        [
+            &ArrayElement::Repeated(
+                Literal::parse("10").unwrap(),
+                Some(Expression::peel(&mut Code::from("0")).unwrap())
+            ),
+            &ArrayElement::Repeated(
+                Literal::parse("5").unwrap(),
+                Some(Expression::peel(&mut Code::from("SOME_CONSTANT")).unwrap())
+            ),
+            &ArrayElement::Repeated(Literal::parse("2").unwrap(), None),
+            &ArrayElement::Repeated(
+                Literal::parse("16#F").unwrap(),
+                Some(Expression::peel(&mut Code::from("1")).unwrap())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn array_of_structures() {
+    let input_output = "[(x := 1, y := 2), 3((x := 0, y := 0)), [1, 2]]";
+    let value = Value::peel(&mut Code::from(input_output)).unwrap();
+    let Value(ValueInner::Array(array)) = &value else {
+        panic!("Expected an array");
+    };
+    assert!(matches!(
+        array.1[1].0,
+        ArrayElement::Repeated(_, Some(Expression(ExpressionInner::Value(_))))
+    ));
+
+    let mut dazzler = dazzle::Dazzler::default();
+    value.dazzle(&mut dazzler);
+    assert_eq!(dazzler.f, input_output);
 }
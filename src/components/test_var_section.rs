@@ -0,0 +1,37 @@
+use super::*;
+
+fn peel(input: &str) -> (VarSection, String) {
+    let mut code = Code::from(input);
+    let section = VarSection::peel(&mut code).unwrap();
+    (section, code.to_string())
+}
+
+#[test]
+fn kinds() {
+    assert_eq!(peel("VAR_TEMP\n").0.kind, VarKind::Temp);
+    assert_eq!(peel("var_in_out\n").0.kind, VarKind::InOut);
+    assert_eq!(peel("VAR\n").0.kind, VarKind::Var);
+    assert!(VarSection::peel(&mut Code::from("VARIABLE")).is_err());
+}
+
+#[test]
+fn qualifiers_any_order() {
+    let (section, rest) = peel("VAR_GLOBAL  persistent\tRETAIN // comment\n");
+
+    assert_eq!(section.kind, VarKind::Global);
+    assert_eq!(
+        section.qualifiers,
+        vec![VarQualifier::Retain, VarQualifier::Persistent]
+    );
+    assert_eq!(rest, " // comment\n");
+}
+
+#[test]
+fn canonical_format() {
+    let mut dazzler = dazzle::Dazzler::default();
+    peel("var_output   non_retain constant")
+        .0
+        .dazzle(&mut dazzler);
+
+    assert_eq!(dazzler.f, "VAR_OUTPUT CONSTANT NON_RETAIN");
+}
@@ -3,16 +3,18 @@ use std::io::{Error, ErrorKind, Result};
 use crate::code::Code;
 use crate::dazzle::{self, Dazzle};
 
-use super::{Ether, Expression, Identifier, KEYWORDS};
+use super::{Ether, Expression, Identifier, Literal, LiteralKind, StringLiteral, KEYWORDS};
 
-#[derive(Debug, PartialEq)]
-pub struct Value(ValueInner);
+#[derive(Clone, Debug, PartialEq)]
+pub struct Value(pub ValueInner);
 
-#[derive(Debug, PartialEq)]
-enum ValueInner {
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValueInner {
     Array(Array),
     Struct(Struct),
-    String(String),
+    String(StringLiteral),
+    Literal(Literal),
+    /// A name, such as a variable or an enumeration member.
     Flat(String),
 }
 
@@ -27,11 +29,8 @@ impl Dazzle for ValueInner {
         match &self {
             Self::Array(inner) => inner.dazzle(dazzler),
             Self::Struct(inner) => inner.dazzle(dazzler),
-            Self::String(inner) => {
-                dazzler.indent_or_space(false);
-                dazzler.f.push_str(&format!("'{inner}'"));
-                dazzler.previous_character = dazzle::PreviousCharacter::Other;
-            }
+            Self::String(inner) => inner.dazzle(dazzler),
+            Self::Literal(inner) => inner.dazzle(dazzler),
             Self::Flat(inner) => inner.dazzle(dazzler),
         }
     }
@@ -41,6 +40,25 @@ impl Value {
     pub fn peel(code: &mut Code) -> Result<Self> {
         Ok(Self(ValueInner::peel(code)?))
     }
+
+    /// The value of an integer literal such as `42`, `-7`, `16#FF` or `UDINT#2#1010`.
+    pub fn integer(&self) -> Option<i64> {
+        match &self.0 {
+            ValueInner::Literal(Literal {
+                kind: LiteralKind::Integer { value, .. },
+                ..
+            }) => i64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// The decoded characters of a `STRING` or `WSTRING` literal.
+    pub fn text(&self) -> Option<&str> {
+        match &self.0 {
+            ValueInner::String(literal) => Some(&literal.value),
+            _ => None,
+        }
+    }
 }
 
 impl ValueInner {
@@ -49,11 +67,12 @@ impl ValueInner {
             Ok(Self::Array(a))
         } else if let Ok(s) = Struct::peel(code) {
             Ok(Self::Struct(s))
-        } else if let Ok(mut code_clone) = code.strip_prefix('\'') {
+        } else if let Some(quote) = code.chars().next().filter(|c| *c == '\'' || *c == '"') {
+            let mut code_clone = code.strip_prefix(quote)?;
             let mut value = String::new();
             let mut escape = false;
             for c in code_clone.chars() {
-                if !escape && c == '\'' {
+                if !escape && c == quote {
                     break;
                 } else if !escape && c == '$' {
                     escape = true;
@@ -65,13 +84,25 @@ impl ValueInner {
                     value.push(c);
                 }
             }
-            code_clone.peel(value.len() + '\''.len_utf8())?;
+            let literal = StringLiteral::parse(&value, quote == '"')
+                .inspect_err(|error| code.report_malformed(error))?;
+            code_clone.peel(value.len() + quote.len_utf8())?;
             *code = code_clone;
-            Ok(Self::String(value))
+            Ok(Self::String(literal))
         } else {
             let mut value = String::new();
-            for c in code.chars() {
-                if c.is_alphanumeric() || c == '_' || c == '-' || c == '#' || c == '.' {
+            let mut chars = code.chars().peekable();
+            while let Some(c) = chars.next() {
+                let next_is_digit = chars.peek().is_some_and(|c| c.is_ascii_digit());
+                if c == '.' && chars.peek() == Some(&'.') {
+                    break;
+                } else if c.is_alphanumeric()
+                    || c == '_'
+                    || c == '#'
+                    || c == '.'
+                    || ((c == '-' || c == '+') && next_is_digit && is_literal_sign(&value))
+                    || (c == ':' && next_is_digit && Literal::continues_with(&value, ':'))
+                {
                     value.push(c);
                 } else {
                     break;
@@ -82,6 +113,11 @@ impl ValueInner {
                     ErrorKind::InvalidData,
                     format!("No value\n{code}"),
                 ))
+            } else if Literal::looks_like(&value) {
+                let literal =
+                    Literal::parse(&value).inspect_err(|error| code.report_malformed(error))?;
+                code.peel(value.len())?;
+                Ok(Self::Literal(literal))
             } else {
                 code.peel(value.len())?;
                 Ok(Self::Flat(value))
@@ -90,41 +126,136 @@ impl ValueInner {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Array(Vec<Ether>, Vec<(Expression, Vec<Ether>)>);
+/// A `-` or `+` belongs to a value if it is part of a typed literal such as `D#2020-01-01` or
+/// `INT#-5`, or in the exponent of a real such as `1.5E-3`, rather than an operator.
+fn is_literal_sign(value: &str) -> bool {
+    let (prefix, digits) = value.rsplit_once('#').unwrap_or(("", value));
+    let is_based = prefix.ends_with(|c: char| c.is_ascii_digit());
+    (digits.is_empty() && !prefix.is_empty())
+        || Literal::continues_with(value, '-')
+        || (!is_based
+            && digits.starts_with(|c: char| c.is_ascii_digit())
+            && digits.ends_with(['e', 'E']))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Array(pub Vec<Ether>, pub Vec<(ArrayElement, Vec<Ether>)>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArrayElement {
+    Single(Expression),
+    /// `count(value)`, repeating `value` `count` times, or the default value if it is omitted.
+    Repeated(Literal, Option<Expression>),
+}
 
 impl Dazzle for Array {
     fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        if dazzler.should_split(self, dazzle_array_singleline) {
+            self.dazzle_multiline(dazzler);
+        } else {
+            dazzle_array_singleline(self, dazzler);
+        }
+    }
+}
+
+fn dazzle_array_singleline(array: &Array, dazzler: &mut dazzle::Dazzler) {
+    let start_with_newline = dazzler.previous_character == dazzle::PreviousCharacter::LineFeed;
+    if start_with_newline {
+        dazzler.indentation_count += 1;
+    }
+    dazzler.indent_or_space(true);
+    dazzler.f.push('[');
+    dazzler.previous_character = dazzle::PreviousCharacter::Other;
+    for ether in &array.0 {
+        ether.dazzle(dazzler);
+    }
+    for (i, (element, ethers)) in array.1.iter().enumerate() {
+        element.dazzle(dazzler);
+        if i + 1 < array.1.len() {
+            dazzler.f.push(',');
+            dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+        }
+        if !ethers.iter().all(|ether| *ether == Ether::LineFeed) {
+            for ether in ethers {
+                ether.dazzle(dazzler);
+            }
+        }
+    }
+    dazzler.indent_or_space(false);
+    dazzler.f.push(']');
+    dazzler.previous_character = dazzle::PreviousCharacter::Other;
+    if start_with_newline {
+        dazzler.indentation_count -= 1;
+    }
+}
+
+impl Array {
+    fn dazzle_multiline(&self, dazzler: &mut dazzle::Dazzler) {
         let start_with_newline = dazzler.previous_character == dazzle::PreviousCharacter::LineFeed;
         if start_with_newline {
             dazzler.indentation_count += 1;
         }
         dazzler.indent_or_space(true);
         dazzler.f.push('[');
+        dazzler.previous_character = dazzle::PreviousCharacter::Other;
         dazzler.indentation_count += 1;
-        for ether in &self.0 {
+        for ether in self.0.iter().filter(|ether| **ether != Ether::LineFeed) {
             ether.dazzle(dazzler);
         }
-        for (i, (value, ethers)) in self.1.iter().enumerate() {
-            value.dazzle(dazzler);
+        dazzler.if_not_linefeed_then_linefeed();
+
+        let width_to_comment_start =
+            self.get_width_to_comment_start(dazzler) + dazzle::INDENT_WIDTH * dazzler.indentation();
+
+        for (i, (element, ethers)) in self.1.iter().enumerate() {
+            element.dazzle(dazzler);
             if i + 1 < self.1.len() {
                 dazzler.f.push(',');
-                dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+                dazzler.previous_character = dazzle::PreviousCharacter::Other;
             }
-            for ether in ethers {
+            for (j, ether) in ethers.iter().enumerate() {
+                if j == 0 && ether.is_comment() {
+                    let width_current = match dazzler.f.lines().last() {
+                        Some(line) => dazzle::width(line),
+                        None => dazzle::width(&dazzler.f),
+                    };
+                    for _ in width_current..width_to_comment_start {
+                        dazzler.f.push(' ');
+                    }
+                }
                 ether.dazzle(dazzler);
             }
+            dazzler.if_not_linefeed_then_linefeed();
         }
         dazzler.indentation_count -= 1;
-        dazzler.indent_or_space(false);
+        dazzler.indent();
         dazzler.f.push(']');
+        dazzler.previous_character = dazzle::PreviousCharacter::Other;
         if start_with_newline {
             dazzler.indentation_count -= 1;
         }
     }
-}
 
-impl Array {
+    fn get_width_to_comment_start(&self, dazzler: &dazzle::Dazzler) -> usize {
+        let mut max_width = 0;
+        for (i, (element, ethers)) in self.1.iter().enumerate() {
+            if !ethers.first().is_some_and(Ether::is_comment) {
+                continue;
+            }
+            let mut dazzler_line = dazzler.on_new_line();
+            element.dazzle(&mut dazzler_line);
+            let mut this_width = match dazzler_line.f.rsplit_once('\n') {
+                Some((_, last_line)) => dazzle::width(last_line),
+                None => dazzle::width(&dazzler_line.f),
+            };
+            if i + 1 < self.1.len() {
+                this_width += 1;
+            }
+            max_width = max_width.max(this_width);
+        }
+        max_width
+    }
+
     fn peel(code: &mut Code) -> Result<Self> {
         let mut code_clone = code.strip_prefix('[')?;
 
@@ -132,7 +263,7 @@ impl Array {
 
         let mut array = Vec::new();
         loop {
-            let member = Expression::peel(&mut code_clone)?;
+            let member = ArrayElement::peel(&mut code_clone)?;
             let mut ethers = Ether::peel(&mut code_clone)?;
             if let Ok(code_clone_stripped) = code_clone.strip_prefix(',') {
                 code_clone = code_clone_stripped;
@@ -148,8 +279,51 @@ impl Array {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct Struct(Vec<(Identifier, Expression, Vec<Ether>)>);
+impl Dazzle for ArrayElement {
+    fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        match self {
+            Self::Single(expression) => expression.dazzle(dazzler),
+            Self::Repeated(count, value) => {
+                count.dazzle(dazzler);
+                dazzler.f.push('(');
+                if let Some(value) = value {
+                    value.dazzle(dazzler);
+                }
+                dazzler.f.push(')');
+                dazzler.previous_character = dazzle::PreviousCharacter::Other;
+            }
+        }
+    }
+}
+
+impl ArrayElement {
+    fn peel(code: &mut Code) -> Result<Self> {
+        if let Some(repeated) = Self::peel_repeated(code) {
+            return Ok(repeated);
+        }
+        Ok(Self::Single(Expression::peel(code)?))
+    }
+
+    fn peel_repeated(code: &mut Code) -> Option<Self> {
+        let mut code_clone = code.clone();
+        let count = match ValueInner::peel(&mut code_clone).ok()? {
+            ValueInner::Literal(
+                literal @ Literal {
+                    kind: LiteralKind::Integer { .. },
+                    ..
+                },
+            ) => literal,
+            _ => return None,
+        };
+        let mut code_clone = code_clone.strip_prefix('(').ok()?.trim_start();
+        let value = Expression::peel(&mut code_clone).ok();
+        *code = code_clone.trim_start().strip_prefix(')').ok()?;
+        Some(Self::Repeated(count, value))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Struct(pub Vec<(Identifier, Expression, Vec<Ether>)>);
 
 impl Dazzle for Struct {
     fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
@@ -183,7 +357,11 @@ fn dazzle_singleline(s: &Struct, dazzler: &mut dazzle::Dazzler) {
 
 impl Struct {
     fn dazzle_multiline(&self, dazzler: &mut dazzle::Dazzler) {
-        dazzler.indentation_count += 1;
+        // An element of a multi-line array already starts on its own line.
+        let indented = dazzler.previous_character != dazzle::PreviousCharacter::LineFeed;
+        if indented {
+            dazzler.indentation_count += 1;
+        }
         dazzler.if_not_linefeed_then_linefeed();
         dazzler.indent();
         dazzler.f.push_str("(\n");
@@ -193,16 +371,17 @@ impl Struct {
         let max_identifier_length = self
             .0
             .iter()
-            .map(|(i, _, _)| i.to_string().len())
+            .map(|(i, _, _)| dazzle::width(&i.to_string()))
             .max()
             .unwrap_or(0);
 
-        let width_to_comment_start = self.get_width_to_comment_start(max_identifier_length)
-            + dazzle::INDENT_WIDTH as usize * dazzler.indentation_count as usize;
+        let width_to_comment_start = self
+            .get_width_to_comment_start(dazzler, max_identifier_length)
+            + dazzle::INDENT_WIDTH * dazzler.indentation();
 
         for (i, (identifier, expression, ethers)) in self.0.iter().enumerate() {
             identifier.dazzle(dazzler);
-            for _ in 0..(max_identifier_length - identifier.to_string().len()) {
+            for _ in 0..(max_identifier_length - dazzle::width(&identifier.to_string())) {
                 dazzler.f.push(' ');
             }
             dazzler.f.push_str(" :=");
@@ -214,8 +393,8 @@ impl Struct {
             for (j, ether) in ethers.iter().enumerate() {
                 if j == 0 && ether.is_comment() {
                     let width_current = match dazzler.f.lines().last() {
-                        Some(line) => line.len(),
-                        None => dazzler.f.len(),
+                        Some(line) => dazzle::width(line),
+                        None => dazzle::width(&dazzler.f),
                     };
                     for _ in width_current..width_to_comment_start {
                         dazzler.f.push(' ');
@@ -228,11 +407,17 @@ impl Struct {
         dazzler.indentation_count -= 1;
         dazzler.indent();
         dazzler.f.push(')');
-        dazzler.indentation_count -= 1;
+        if indented {
+            dazzler.indentation_count -= 1;
+        }
         dazzler.previous_character = dazzle::PreviousCharacter::Other;
     }
 
-    fn get_width_to_comment_start(&self, max_identifier_length: usize) -> usize {
+    fn get_width_to_comment_start(
+        &self,
+        dazzler: &dazzle::Dazzler,
+        max_identifier_length: usize,
+    ) -> usize {
         let mut max_width = 0;
         for (i, (_, expression, ethers)) in self.0.iter().enumerate() {
             match ethers.first() {
@@ -243,15 +428,11 @@ impl Struct {
                 }
                 None => continue,
             }
-            let mut dazzler_line = dazzle::Dazzler {
-                f: String::new(),
-                previous_character: dazzle::PreviousCharacter::LineFeed,
-                indentation_count: 0,
-            };
+            let mut dazzler_line = dazzler.on_new_line();
             expression.dazzle(&mut dazzler_line);
             let mut this_width = match dazzler_line.f.rsplit_once('\n') {
-                Some((_, last_line)) => last_line.len(),
-                None => dazzler_line.f.len() + max_identifier_length + " := ".len(),
+                Some((_, last_line)) => dazzle::width(last_line),
+                None => dazzle::width(&dazzler_line.f) + max_identifier_length + " := ".len(),
             };
             if i + 1 < self.0.len() {
                 this_width += 1;
@@ -0,0 +1,159 @@
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+
+use crate::code::Code;
+use crate::dazzle::{self, Dazzle};
+
+use super::Spelling;
+
+/// The header of a `VAR ... END_VAR` block, such as `VAR_INPUT CONSTANT`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VarSection {
+    pub kind: VarKind,
+    pub qualifiers: Vec<VarQualifier>,
+    /// How the kind, then each of the qualifiers, was written.
+    pub spellings: Vec<Spelling>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VarKind {
+    Var,
+    Input,
+    Output,
+    InOut,
+    Global,
+    Temp,
+    Stat,
+    External,
+    Config,
+    Inst,
+}
+
+/// Qualifiers are kept in this order when formatted.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub enum VarQualifier {
+    Constant,
+    Retain,
+    NonRetain,
+    Persistent,
+}
+
+impl VarKind {
+    const ALL: [Self; 10] = [
+        Self::Var,
+        Self::Input,
+        Self::Output,
+        Self::InOut,
+        Self::Global,
+        Self::Temp,
+        Self::Stat,
+        Self::External,
+        Self::Config,
+        Self::Inst,
+    ];
+
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Var => "VAR",
+            Self::Input => "VAR_INPUT",
+            Self::Output => "VAR_OUTPUT",
+            Self::InOut => "VAR_IN_OUT",
+            Self::Global => "VAR_GLOBAL",
+            Self::Temp => "VAR_TEMP",
+            Self::Stat => "VAR_STAT",
+            Self::External => "VAR_EXTERNAL",
+            Self::Config => "VAR_CONFIG",
+            Self::Inst => "VAR_INST",
+        }
+    }
+}
+
+impl VarQualifier {
+    const ALL: [Self; 4] = [
+        Self::Constant,
+        Self::Retain,
+        Self::NonRetain,
+        Self::Persistent,
+    ];
+
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Constant => "CONSTANT",
+            Self::Retain => "RETAIN",
+            Self::NonRetain => "NON_RETAIN",
+            Self::Persistent => "PERSISTENT",
+        }
+    }
+}
+
+impl fmt::Display for VarKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.keyword())
+    }
+}
+
+impl fmt::Display for VarQualifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.keyword())
+    }
+}
+
+impl Dazzle for VarSection {
+    fn dazzle(&self, dazzler: &mut dazzle::Dazzler) {
+        let spelling = |i: usize| self.spellings.get(i).cloned().unwrap_or_default();
+        dazzler.keyword(self.kind.keyword(), &spelling(0));
+        for (i, qualifier) in self.qualifiers.iter().enumerate() {
+            dazzler.previous_character = dazzle::PreviousCharacter::PendingSpace;
+            dazzler.keyword(qualifier.keyword(), &spelling(i + 1));
+        }
+    }
+}
+
+impl VarSection {
+    pub fn peel(code: &mut Code) -> Result<Self> {
+        let (kind, mut code_clone) = VarKind::ALL
+            .iter()
+            .find_map(|kind| {
+                code.strip_prefix_keyword(kind.keyword())
+                    .ok()
+                    .map(|code_stripped| (*kind, code_stripped))
+            })
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("No VAR section\n{code}")))?;
+        let kind_spelling = Spelling::between(code, &code_clone);
+
+        let mut qualifiers: Vec<(VarQualifier, Spelling)> = Vec::new();
+        'qualifiers: loop {
+            let code_spaced = code_clone.trim_start_spaces();
+            if code_spaced.position() == code_clone.position() {
+                break;
+            }
+            for qualifier in VarQualifier::ALL {
+                if let Ok(code_stripped) = code_spaced.strip_prefix_keyword(qualifier.keyword()) {
+                    if !qualifiers.iter().any(|(q, _)| *q == qualifier) {
+                        qualifiers
+                            .push((qualifier, Spelling::between(&code_spaced, &code_stripped)));
+                    }
+                    code_clone = code_stripped;
+                    continue 'qualifiers;
+                }
+            }
+            break;
+        }
+        qualifiers.sort_by_key(|(qualifier, _)| *qualifier);
+        let (qualifiers, qualifier_spellings): (Vec<_>, Vec<_>) = qualifiers.into_iter().unzip();
+
+        *code = code_clone;
+        Ok(Self {
+            kind,
+            qualifiers,
+            spellings: [kind_spelling]
+                .into_iter()
+                .chain(qualifier_spellings)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "./test_var_section.rs"]
+mod test_var_section;
@@ -1,6 +1,15 @@
 use std::fmt;
 
-pub const INDENT_WIDTH: u8 = 4;
+use crate::components::Spelling;
+use crate::fmt::KeywordCase;
+
+pub const INDENT_WIDTH: usize = 4;
+
+/// The number of characters in `text`, which is not its length in bytes once it holds characters
+/// such as `ä` or `°`. Wide characters, such as those of CJK scripts, still count as one.
+pub fn width(text: &str) -> usize {
+    text.chars().count()
+}
 
 pub trait Dazzle {
     fn dazzle(&self, dazzler: &mut Dazzler);
@@ -10,7 +19,12 @@ pub trait Dazzle {
 pub struct Dazzler {
     pub f: String,
     pub previous_character: PreviousCharacter,
-    pub indentation_count: u8,
+    pub indentation_count: usize,
+    /// The indentation of each open `{IF}` pragma, whose contents are indented one level further.
+    pub conditionals: Vec<usize>,
+    /// Whether prose `(* *)` comments are refilled to the line length limit.
+    pub reflow_comments: bool,
+    pub keyword_case: KeywordCase,
 }
 
 #[derive(Clone, PartialEq)]
@@ -27,6 +41,9 @@ impl Default for Dazzler {
             f: String::new(),
             previous_character: PreviousCharacter::Top,
             indentation_count: 0,
+            conditionals: Vec::new(),
+            reflow_comments: false,
+            keyword_case: KeywordCase::default(),
         }
     }
 }
@@ -58,8 +75,31 @@ impl Dazzler {
         }
     }
 
+    /// Writes `keyword`, given in upper case, as the keyword case policy asks.
+    pub fn keyword(&mut self, keyword: &str, spelling: &Spelling) {
+        self.indent_or_space(false);
+        self.f.push_str(&self.keyword_case.apply(keyword, spelling));
+        self.previous_character = PreviousCharacter::Other;
+    }
+
+    /// Writes text whose keywords `write` spells as the keyword case policy asks, such as a data
+    /// type.
+    pub fn cased(&mut self, write: impl FnOnce(&mut String, KeywordCase) -> fmt::Result) {
+        self.indent_or_space(false);
+        let mut text = String::new();
+        // Writing to a `String` cannot fail.
+        let _ = write(&mut text, self.keyword_case);
+        self.f.push_str(&text);
+        self.previous_character = PreviousCharacter::Other;
+    }
+
+    /// The number of levels a new line is indented by.
+    pub fn indentation(&self) -> usize {
+        self.indentation_count + self.conditionals.len()
+    }
+
     pub fn indent(&mut self) {
-        for _ in 0..self.indentation_count {
+        for _ in 0..self.indentation() {
             self.f.push_str("    ");
         }
         self.previous_character = PreviousCharacter::Other;
@@ -75,19 +115,34 @@ impl Dazzler {
         }
     }
 
-    pub fn should_split<T>(&self, t: &T, dazzle_singleline: fn(&T, &mut Dazzler)) -> bool {
-        let last_line = match self.f.lines().last() {
-            Some(line) => line.to_owned(),
-            None => self.f.clone(),
-        };
-        let mut dazzler = Dazzler {
-            f: last_line,
+    /// A copy to measure with, holding only the line written so far.
+    pub fn on_current_line(&self) -> Self {
+        Self {
+            f: self.f.rsplit('\n').next().unwrap_or_default().to_owned(),
             previous_character: self.previous_character.clone(),
             indentation_count: self.indentation_count,
-        };
+            conditionals: self.conditionals.clone(),
+            reflow_comments: self.reflow_comments,
+            keyword_case: self.keyword_case,
+        }
+    }
+
+    /// An empty, unindented dazzler to measure with, at the start of a line and with the same
+    /// options.
+    pub fn on_new_line(&self) -> Self {
+        Self {
+            previous_character: PreviousCharacter::LineFeed,
+            reflow_comments: self.reflow_comments,
+            keyword_case: self.keyword_case,
+            ..Default::default()
+        }
+    }
+
+    pub fn should_split<T>(&self, t: &T, dazzle_singleline: fn(&T, &mut Dazzler)) -> bool {
+        let mut dazzler = self.on_current_line();
 
         dazzle_singleline(t, &mut dazzler);
 
-        dazzler.f.contains('\n') || dazzler.f.len() > crate::fmt::LINE_LENGTH_LIMIT as usize
+        dazzler.f.contains('\n') || width(&dazzler.f) > crate::fmt::LINE_LENGTH_LIMIT
     }
 }
@@ -2,12 +2,66 @@ use std::io::{Error, ErrorKind, Result};
 use std::str::FromStr;
 
 use crate::code::Code;
-use crate::components::{Component as C, Ether};
+use crate::components::{
+    Attribute, BeginMiddleEnd, Component as C, DataType, Ether, Expression, Identifier, VarSection,
+};
 use crate::layout::{string_and_format_get_items, Layout as L};
+use crate::visit::Visit;
 
 #[derive(Debug)]
 pub struct Declaration(pub Vec<C>);
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumMember {
+    pub name: Identifier,
+    pub value: Option<Expression>,
+    /// The member's integer value: its own if constant, otherwise one more than the previous
+    /// member's, starting from zero.
+    pub resolved: Option<i64>,
+}
+
+/// A variable of a `VAR ... END_VAR` block, or a member of a structure or union.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: Identifier,
+    pub data_type: DataType,
+    /// The block the variable is declared in, or `None` for a member.
+    pub section: Option<VarSection>,
+    pub attributes: Vec<Attribute>,
+    /// The comment lines directly above the variable followed by any comment after it on the same
+    /// line.
+    pub doc: Option<String>,
+}
+
+/// A `VAR ... END_VAR` block with the attributes written above it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VarBlock {
+    pub section: VarSection,
+    pub attributes: Vec<Attribute>,
+    /// The comment lines directly above the block.
+    pub doc: Option<String>,
+    pub variables: Vec<Variable>,
+}
+
+impl Variable {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        find_attribute(&self.attributes, name)
+    }
+}
+
+impl VarBlock {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        find_attribute(&self.attributes, name)
+    }
+}
+
+/// Attribute names are compared ignoring case.
+fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name.eq_ignore_ascii_case(name))
+}
+
 impl FromStr for Declaration {
     type Err = Error;
     fn from_str(input: &str) -> Result<Self> {
@@ -19,6 +73,8 @@ impl FromStr for Declaration {
             Ok(declaration)
         } else if let Ok(declaration) = string_and_layout_get_declaration(&mut code, UNION) {
             Ok(declaration)
+        } else if let Ok(declaration) = string_and_layout_get_declaration(&mut code, ALIAS) {
+            Ok(declaration)
         } else if let Ok(declaration) =
             string_and_layout_get_declaration(&mut code, GLOBAL_VARIABLE_LIST)
         {
@@ -32,14 +88,323 @@ impl FromStr for Declaration {
         {
             Ok(declaration)
         } else {
-            Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("Cannot parse \n{input}"),
-            ))
+            Err(code.malformed().unwrap_or_else(|| {
+                Error::new(ErrorKind::InvalidData, format!("Cannot parse \n{input}"))
+            }))
+        }
+    }
+}
+
+impl Declaration {
+    /// The name of the type or POU, or `None` for a global variable list.
+    pub fn name(&self) -> Option<&Identifier> {
+        self.0.iter().find_map(|component| match component {
+            C::Identifier(identifier) => Some(identifier),
+            C::BeginMiddleEnd(block) => block.begin.iter().find_map(|c| match c {
+                C::Identifier(identifier) => Some(identifier),
+                _ => None,
+            }),
+            _ => None,
+        })
+    }
+
+    /// The type or function block named after `EXTENDS`.
+    pub fn extends(&self) -> Option<String> {
+        let mut header = self.0.iter().flat_map(|component| match component {
+            C::BeginMiddleEnd(block) => block.begin.as_slice(),
+            other => std::slice::from_ref(other),
+        });
+        header.find(|component| matches!(component, C::Uppercase("EXTENDS", _)))?;
+        header.find_map(|component| match component {
+            C::Identifier(identifier) => Some(identifier.0.clone()),
+            C::IdentifierSub(identifier) => Some(identifier.0.clone()),
+            _ => None,
+        })
+    }
+
+    /// The type a function, method or property returns, or the type an alias stands for.
+    pub fn data_type(&self) -> Option<&DataType> {
+        self.0.iter().find_map(|component| match component {
+            C::DataType(data_type) => Some(data_type),
+            _ => None,
+        })
+    }
+
+    /// The members of an enumeration type, or nothing for other declarations.
+    pub fn enum_members(&self) -> Vec<EnumMember> {
+        let mut members: Vec<EnumMember> = Vec::new();
+        for component in &self.0 {
+            let C::BeginMiddleEnd(block) = component else {
+                continue;
+            };
+            let is_enum = block
+                .begin
+                .iter()
+                .rev()
+                .find(|c| !matches!(c, C::Ether(_)))
+                .is_some_and(|c| matches!(c, C::Text("(")));
+            if !is_enum {
+                continue;
+            }
+            for items in &block.middle {
+                let mut name = None;
+                let mut value = None;
+                for item in items {
+                    match item {
+                        C::Identifier(identifier) if name.is_none() => {
+                            name = Some(identifier.clone())
+                        }
+                        C::Expression(expression) => value = Some(expression.clone()),
+                        _ => (),
+                    }
+                }
+                let Some(name) = name else {
+                    continue;
+                };
+                let resolved = match &value {
+                    Some(expression) => expression.evaluate_integer(&|text| {
+                        members
+                            .iter()
+                            .find(|member| member.name.0.eq_ignore_ascii_case(text))
+                            .and_then(|member| member.resolved)
+                    }),
+                    None => match members.last() {
+                        Some(previous) => previous.resolved.and_then(|v| v.checked_add(1)),
+                        None => Some(0),
+                    },
+                };
+                members.push(EnumMember {
+                    name,
+                    value,
+                    resolved,
+                });
+            }
         }
+        members
+    }
+
+    /// The attributes written above the type or POU.
+    pub fn attributes(&self) -> Vec<Attribute> {
+        self.annotate().attributes
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<Attribute> {
+        find_attribute(&self.attributes(), name).cloned()
+    }
+
+    /// The comment lines directly above the type or POU.
+    pub fn doc(&self) -> Option<String> {
+        self.annotate().doc
+    }
+
+    /// Every `VAR ... END_VAR` block with its attributes and variables, in order.
+    pub fn var_blocks(&self) -> Vec<VarBlock> {
+        self.annotate().var_blocks
+    }
+
+    /// Every variable and structure or union member, in order.
+    pub fn variables(&self) -> Vec<Variable> {
+        self.annotate().variables
+    }
+
+    pub fn variables_with_attribute(&self, name: &str) -> Vec<Variable> {
+        self.variables()
+            .into_iter()
+            .filter(|variable| variable.attribute(name).is_some())
+            .collect()
+    }
+
+    fn annotate(&self) -> Annotations {
+        let mut annotations = Annotations::default();
+        annotations.walk(&self.0);
+        annotations
+    }
+
+    /// The headers of every `VAR ... END_VAR` block, in order.
+    pub fn var_sections(&self) -> Vec<VarSection> {
+        struct Collector(Vec<VarSection>);
+        impl Visit for Collector {
+            fn visit_var_section(&mut self, var_section: &VarSection) {
+                self.0.push(var_section.clone());
+            }
+        }
+
+        let mut collector = Collector(Vec::new());
+        collector.visit_declaration(self);
+        collector.0
     }
 }
 
+/// Attaches each attribute pragma and comment to the element it belongs to.
+#[derive(Default)]
+struct Annotations {
+    attributes: Vec<Attribute>,
+    doc: Option<String>,
+    var_blocks: Vec<VarBlock>,
+    variables: Vec<Variable>,
+    pending_attributes: Vec<Attribute>,
+    pending_comments: Vec<String>,
+    /// The first comment after the latest element on its line.
+    trailing_comment: Option<String>,
+    on_same_line: bool,
+    previous_was_line_feed: bool,
+    found_first_element: bool,
+}
+
+impl Annotations {
+    fn walk(&mut self, components: &[C]) {
+        for component in components {
+            match component {
+                C::Ether(ether) => self.ether(ether),
+                C::Repeat(inners) | C::Statement(_, inners) => self.walk(inners),
+                C::BeginMiddleEnd(block) => self.walk_block(block),
+                _ => {
+                    let (attributes, comments) = self.element();
+                    if !self.found_first_element {
+                        self.found_first_element = true;
+                        self.attributes = attributes;
+                        self.doc = doc(comments, None);
+                    }
+                }
+            }
+        }
+    }
+
+    fn ether(&mut self, ether: &Ether) {
+        if self.on_same_line {
+            if let Some(comment) = ether.comment() {
+                self.trailing_comment
+                    .get_or_insert_with(|| comment.to_string());
+            }
+        } else if let Some(attribute) = ether.attribute() {
+            self.pending_attributes.push(attribute.clone());
+        } else if let Some(comment) = ether.comment() {
+            self.pending_comments.push(comment.to_string());
+        } else if *ether == Ether::LineFeed && self.previous_was_line_feed {
+            // A blank line separates comments from whatever follows.
+            self.pending_comments.clear();
+        }
+        if *ether == Ether::LineFeed {
+            self.on_same_line = false;
+        }
+        self.previous_was_line_feed = *ether == Ether::LineFeed;
+    }
+
+    /// Takes the attributes and comments above the element just found, if it starts a line.
+    fn element(&mut self) -> (Vec<Attribute>, Vec<String>) {
+        self.previous_was_line_feed = false;
+        if self.on_same_line {
+            return (Vec::new(), Vec::new());
+        }
+        self.on_same_line = true;
+        self.trailing_comment = None;
+        (
+            std::mem::take(&mut self.pending_attributes),
+            std::mem::take(&mut self.pending_comments),
+        )
+    }
+
+    fn walk_block(&mut self, block: &BeginMiddleEnd) {
+        let section = block.begin.iter().find_map(|component| match component {
+            C::VarSection(section) => Some(section.clone()),
+            _ => None,
+        });
+        let has_members = block
+            .begin
+            .iter()
+            .any(|component| matches!(component, C::Uppercase("STRUCT" | "UNION", _)));
+        if section.is_none() && !has_members {
+            self.walk(&block.begin);
+            for item in &block.middle {
+                self.walk(item);
+            }
+            self.walk(&block.end);
+            return;
+        }
+
+        let mut header = None;
+        for component in &block.begin {
+            match component {
+                C::Ether(ether) => self.ether(ether),
+                _ => {
+                    let first_element = !self.found_first_element;
+                    let annotations = self.element();
+                    if first_element {
+                        self.found_first_element = true;
+                        self.attributes = annotations.0.clone();
+                        self.doc = doc(annotations.1.clone(), None);
+                    }
+                    header.get_or_insert(annotations);
+                }
+            }
+        }
+        let (section_attributes, section_comments) = header.unwrap_or_default();
+        let section_doc = doc(section_comments, self.trailing_comment.take());
+
+        let mut variables = Vec::new();
+        for item in &block.middle {
+            // Attributes and comments written above a variable may trail the item before it.
+            let mut leading = None;
+            for component in item {
+                match component {
+                    C::Ether(ether) => self.ether(ether),
+                    _ => {
+                        let annotations = self.element();
+                        leading.get_or_insert(annotations);
+                    }
+                }
+            }
+            let data_type = item.iter().find_map(|component| match component {
+                C::DataType(data_type) => Some(data_type),
+                _ => None,
+            });
+            let (Some((attributes, comments)), Some(data_type)) = (leading, data_type) else {
+                continue;
+            };
+            let doc = doc(comments, self.trailing_comment.take());
+            for name in variable_names(item) {
+                variables.push(Variable {
+                    name,
+                    data_type: data_type.clone(),
+                    section: section.clone(),
+                    attributes: attributes.clone(),
+                    doc: doc.clone(),
+                });
+            }
+        }
+        self.walk(&block.end);
+
+        self.variables.extend(variables.iter().cloned());
+        if let Some(section) = section {
+            self.var_blocks.push(VarBlock {
+                section,
+                attributes: section_attributes,
+                doc: section_doc,
+                variables,
+            });
+        }
+    }
+}
+
+fn doc(leading: Vec<String>, trailing: Option<String>) -> Option<String> {
+    let lines: Vec<String> = leading.into_iter().chain(trailing).collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// The names declared by a line such as `a, b : INT;`.
+fn variable_names(item: &[C]) -> Vec<Identifier> {
+    let mut names = Vec::new();
+    for component in item {
+        match component {
+            C::Identifier(name) => names.push(name.clone()),
+            C::Repeat(inners) => names.extend(variable_names(inners)),
+            C::Text(":") => break,
+            _ => (),
+        }
+    }
+    names
+}
+
 fn string_and_layout_get_declaration(code: &mut Code, layout: &[L]) -> Result<Declaration> {
     let mut items = string_and_format_get_items(code, layout)?;
     for ether in Ether::peel(code)? {
@@ -66,6 +431,7 @@ const VARIABLE_DECLARATION: &[L] = &[L::OneOf(&[
         L::Text(":"),
         L::Space,
         L::DataType,
+        L::Option(&[L::Arguments]),
         L::Option(&[L::Space, L::Text(":="), L::Space, L::Expression]),
         L::Text(";"),
     ],
@@ -79,9 +445,9 @@ const ENUM: &[L] = &[
             L::Uppercase("TYPE"), L::Space, L::Identifier, L::Space, L::Text(":"), L::LineFeed,
             L::Text("("),
         ],
-        &[L::Identifier, L::Option(&[L::Space, L::Text(":="), L::Space, L::Value]), L::Option(&[L::Text(",")])],
+        &[L::Identifier, L::Option(&[L::Space, L::Text(":="), L::Space, L::Expression]), L::Option(&[L::Text(",")])],
         &[
-            L::Text(")"), L::Text(";"), L::LineFeed,
+            L::Text(")"), L::Option(&[L::Space, L::DataType]), L::Text(";"), L::LineFeed,
             L::Uppercase("END_TYPE"),
         ],
     ),
@@ -117,14 +483,16 @@ const UNION: &[L] = &[
     ),
 ];
 
+/// An alias or subrange, such as `TYPE T_Percent : INT(0..100);`.
+#[rustfmt::skip]
+const ALIAS: &[L] = &[
+    L::Uppercase("TYPE"), L::Space, L::Identifier, L::Space, L::Text(":"), L::Space, L::DataType,
+    L::Option(&[L::Space, L::Text(":="), L::Space, L::Expression]), L::Text(";"), L::LineFeed,
+    L::Uppercase("END_TYPE"),
+];
+
 const GLOBAL_VARIABLE_LIST: &[L] = &[L::BeginMiddleEnd(
-    &[
-        L::Uppercase("VAR_GLOBAL"),
-        L::Option(&[L::OneOf(&[
-            &[L::Space, L::Uppercase("CONSTANT")],
-            &[L::Space, L::Uppercase("PERSISTENT")],
-        ])]),
-    ],
+    &[L::VarSection],
     VARIABLE_DECLARATION,
     &[L::Uppercase("END_VAR")],
 )];
@@ -133,13 +501,23 @@ const GLOBAL_VARIABLE_LIST: &[L] = &[L::BeginMiddleEnd(
 const PROGRAM_ORGANISATION_UNIT: &[L] = &[
     L::OneOf(&[
         &[L::Uppercase("PROGRAM"), L::Space, L::Identifier],
-        &[L::Uppercase("FUNCTION_BLOCK"), L::Space, L::Option(&[L::Uppercase("PUBLIC"), L::Space]), L::Identifier, L::Option(&[L::Space, L::Uppercase("EXTENDS"), L::Space, L::IdentifierSub])],
+        &[
+            L::Uppercase("FUNCTION_BLOCK"),
+            L::Space,
+            L::OneOf(&[
+                &[L::OneOf(INHERITANCE), L::Space, L::OneOf(FUNCTION_BLOCK_ACCESS), L::Space],
+                &[L::Option(&[L::OneOf(FUNCTION_BLOCK_ACCESS), L::Space]), L::Option(&[L::OneOf(INHERITANCE), L::Space])],
+            ]),
+            L::Identifier,
+            L::Option(&[L::Space, L::Uppercase("EXTENDS"), L::Space, L::IdentifierSub]),
+            L::Option(&[L::Space, L::Uppercase("IMPLEMENTS"), L::Space, L::IdentifierList]),
+        ],
         &[
             L::OneOf(&[&[L::Uppercase("METHOD")], &[L::Uppercase("PROPERTY")]]),
             L::Space,
-            L::Option(&[
-                L::OneOf(&[&[L::Uppercase("PRIVATE")], &[L::Uppercase("PROTECTED")], &[L::Uppercase("PUBLIC")], &[L::Uppercase("INTERNAL")]]),
-                L::Space,
+            L::OneOf(&[
+                &[L::OneOf(INHERITANCE), L::Space, L::OneOf(MEMBER_ACCESS), L::Space],
+                &[L::Option(&[L::OneOf(MEMBER_ACCESS), L::Space]), L::Option(&[L::OneOf(INHERITANCE), L::Space])],
             ]),
             L::Identifier,
             L::Option(&[L::Space, L::Text(":"), L::Space, L::DataType, L::Option(&[L::Text(";")])]),
@@ -153,32 +531,26 @@ const PROGRAM_ORGANISATION_UNIT: &[L] = &[
     ]),
     L::LineFeed,
     L::Repeat(&[L::BeginMiddleEnd(
-        &[
-            L::OneOf(&[
-                &[L::Uppercase("VAR_INPUT")],
-                &[L::Uppercase("VAR_IN_OUT")],
-                &[L::Uppercase("VAR_OUTPUT")],
-                &[L::Uppercase("VAR CONSTANT")],
-                &[L::Uppercase("VAR PERSISTENT")],
-                &[L::Uppercase("VAR RETAIN")],
-                &[L::Uppercase("VAR_INST")],
-                &[L::Uppercase("VAR")],
-                &[L::Uppercase("VAR_GLOBAL")],
-            ]),
-        ],
+        &[L::VarSection],
         VARIABLE_DECLARATION,
         &[L::Uppercase("END_VAR")],
     )]),
 ];
 
+/// At most one of these, and at most one of [`INHERITANCE`], may precede the name of a POU.
+const FUNCTION_BLOCK_ACCESS: &[&[L]] = &[&[L::Uppercase("PUBLIC")], &[L::Uppercase("INTERNAL")]];
+
+const MEMBER_ACCESS: &[&[L]] = &[
+    &[L::Uppercase("PRIVATE")],
+    &[L::Uppercase("PROTECTED")],
+    &[L::Uppercase("PUBLIC")],
+    &[L::Uppercase("INTERNAL")],
+];
+
+const INHERITANCE: &[&[L]] = &[&[L::Uppercase("ABSTRACT")], &[L::Uppercase("FINAL")]];
+
 const PROPERTY_GET_OR_SET: &[L] = &[L::Repeat(&[L::BeginMiddleEnd(
-    &[L::OneOf(&[
-        &[L::Uppercase("VAR CONSTANT")],
-        &[L::Uppercase("VAR PERSISTENT")],
-        &[L::Uppercase("VAR RETAIN")],
-        &[L::Uppercase("VAR_INST")],
-        &[L::Uppercase("VAR")],
-    ])],
+    &[L::VarSection],
     VARIABLE_DECLARATION,
     &[L::Uppercase("END_VAR")],
 )])];
@@ -4,15 +4,26 @@ use std::str::FromStr;
 use crate::components::{BeginMiddleEnd, Component as C, Ether};
 use crate::dazzle::{self, Dazzle, PreviousCharacter};
 use crate::declaration::Declaration;
+use crate::visit::VisitMut;
 
-pub(super) fn align(input: &str) -> Result<String> {
-    Ok(Declaration::from_str(input)?.make_pretty())
+use super::{NormalizeLiterals, Options};
+
+pub(super) fn align(input: &str, options: &Options) -> Result<String> {
+    let mut declaration = Declaration::from_str(input)?;
+    if options.normalize_literals {
+        NormalizeLiterals.visit_declaration_mut(&mut declaration);
+    }
+    Ok(declaration.make_pretty(options))
 }
 
 impl Declaration {
-    fn make_pretty(&mut self) -> String {
+    fn make_pretty(&mut self, options: &Options) -> String {
         self.trim_line_feeds();
 
+        let mut max_width = 0;
+        self.extend_to_width("AT", &mut max_width, false);
+        self.extend_to_width("AT", &mut max_width, true);
+
         let mut max_width = 0;
         self.extend_to_width(":", &mut max_width, false);
         self.extend_to_width(":", &mut max_width, true);
@@ -25,7 +36,11 @@ impl Declaration {
         self.extend_to_width("//", &mut max_width, false);
         self.extend_to_width("//", &mut max_width, true);
 
-        let mut dazzler = dazzle::Dazzler::default();
+        let mut dazzler = dazzle::Dazzler {
+            reflow_comments: options.reflow_comments,
+            keyword_case: options.keyword_case,
+            ..Default::default()
+        };
         for component in &self.0 {
             component.dazzle(&mut dazzler);
         }
@@ -36,7 +51,7 @@ impl Declaration {
         trim_line_feeds(&mut self.0, false);
     }
 
-    fn extend_to_width(&mut self, aligner: &str, width_max: &mut u8, found_max: bool) {
+    fn extend_to_width(&mut self, aligner: &str, width_max: &mut usize, found_max: bool) {
         let mut dazzler = dazzle::Dazzler::default();
         extend_to_width(
             &mut self.0,
@@ -72,23 +87,29 @@ fn trim_line_feeds(components: &mut Vec<C>, allow_multiple: bool) {
             }
             C::Ether(_)
             | C::Space
-            | C::Uppercase(_)
+            | C::Uppercase(..)
             | C::Text(_)
             | C::Identifier(_)
             | C::IdentifierSub(_)
+            | C::IdentifierList(_)
             | C::Address(_)
+            | C::Arguments(_)
+            | C::CaseLabels(_)
             | C::DataType(_)
             | C::Value(_)
+            | C::VarSection(_)
             | C::Expression(_)
             | C::Filler(_) => {
                 line_feed_count = 0;
                 i += 1;
             }
-            C::Repeat(inners) => {
+            C::Repeat(inners) | C::Statement(_, inners) => {
                 trim_line_feeds(inners, false);
                 i += 1;
             }
-            C::BeginMiddleEnd(BeginMiddleEnd { begin, middle, end }) => {
+            C::BeginMiddleEnd(BeginMiddleEnd {
+                begin, middle, end, ..
+            }) => {
                 trim_line_feeds(begin, false);
                 let n_middles = middle.len();
                 for (im, m) in middle.iter_mut().enumerate() {
@@ -106,7 +127,7 @@ fn extend_to_width(
     dazzler: &mut dazzle::Dazzler,
     aligner: &str,
     inside_repeat: bool,
-    width_max: &mut u8,
+    width_max: &mut usize,
     found_max: bool,
 ) {
     let mut i = 0;
@@ -126,8 +147,7 @@ fn extend_to_width(
                     && inner.is_comment()
                     && next_is_line_feed
                 {
-                    if let Some(last_line) = dazzler.f.lines().last() {
-                        let width = last_line.len() as u8;
+                    if let Some(width) = line_width(dazzler) {
                         if !found_max {
                             if width > *width_max {
                                 *width_max = width;
@@ -145,7 +165,7 @@ fn extend_to_width(
                 }
             }
             C::Space
-            | C::Uppercase(_)
+            | C::Uppercase(..)
             | C::Identifier(_)
             | C::IdentifierSub(_)
             | C::DataType(_)
@@ -155,30 +175,40 @@ fn extend_to_width(
                 let text = inner.to_string();
                 component.dazzle(dazzler);
                 if inside_repeat && *text == *aligner {
-                    if let Some(last_line) = dazzler.f.lines().last() {
-                        let width = last_line.len() as u8;
+                    if let Some(width) = line_width(dazzler) {
                         if !found_max {
                             if width > *width_max {
                                 *width_max = width;
                             }
                         } else if width < *width_max {
                             let difference = *width_max - width;
-                            if aligner.eq(":") && i >= 2 {
-                                if let C::Address(_) = components[i - 2] {
-                                    components.insert(i - 2, C::Filler(difference));
-                                } else {
-                                    components.insert(i, C::Filler(difference));
-                                }
-                            } else {
-                                components.insert(i, C::Filler(difference));
+                            components.insert(i, C::Filler(difference));
+                            i += 1;
+                        }
+                    }
+                }
+            }
+            C::Address(_) => {
+                if inside_repeat && *aligner == *"AT" {
+                    if let Some(width) = line_width(dazzler) {
+                        if !found_max {
+                            if width > *width_max {
+                                *width_max = width;
                             }
+                        } else if width < *width_max {
+                            components.insert(i, C::Filler(*width_max - width));
                             i += 1;
                         }
                     }
                 }
+                components[i].dazzle(dazzler);
+            }
+            C::Arguments(_) | C::CaseLabels(_) | C::IdentifierList(_) | C::VarSection(_) => {
+                component.dazzle(dazzler)
             }
-            C::Address(_) => component.dazzle(dazzler),
-            C::BeginMiddleEnd(BeginMiddleEnd { begin, middle, end }) => {
+            C::BeginMiddleEnd(BeginMiddleEnd {
+                begin, middle, end, ..
+            }) => {
                 for b in begin {
                     b.dazzle(dazzler);
                 }
@@ -189,7 +219,7 @@ fn extend_to_width(
                     e.dazzle(dazzler);
                 }
             }
-            C::Repeat(ref mut inner) => {
+            C::Repeat(ref mut inner) | C::Statement(_, ref mut inner) => {
                 extend_to_width(inner, dazzler, aligner, true, width_max, found_max);
             }
             C::Filler(n) => {
@@ -202,6 +232,14 @@ fn extend_to_width(
     }
 }
 
+/// The width of the line so far, leaving out the indentation of any conditional pragmas around it,
+/// so that lines in a `{IF}` branch align with those outside.
+fn line_width(dazzler: &dazzle::Dazzler) -> Option<usize> {
+    let last_line = dazzler.f.lines().last()?;
+    let conditional_indentation = dazzle::INDENT_WIDTH * dazzler.conditionals.len();
+    Some(dazzle::width(last_line).saturating_sub(conditional_indentation))
+}
+
 #[cfg(test)]
 #[path = "./test_declaration.rs"]
 mod test_declaration;
@@ -0,0 +1,563 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Result;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::components::{
+    Argument, BeginMiddleEnd, Component as C, DataType, Ether, ExpressionInner, Member, Spelling,
+    ValueInner,
+};
+use crate::declaration::Declaration;
+use crate::implementation::Implementation;
+use crate::structured_text::{self, Content};
+use crate::visit::{self, VisitMut};
+use crate::visit_dirs;
+
+/// Functions every project can call without declaring them.
+const STANDARD_FUNCTIONS: &[&str] = &[
+    "ABS",
+    "ACOS",
+    "ADD",
+    "ADR",
+    "ASIN",
+    "ATAN",
+    "ATAN2",
+    "BITADR",
+    "CONCAT",
+    "COS",
+    "DELETE",
+    "DIV",
+    "EXP",
+    "EXPT",
+    "FIND",
+    "INSERT",
+    "LEFT",
+    "LEN",
+    "LIMIT",
+    "LN",
+    "LOG",
+    "MAX",
+    "MID",
+    "MIN",
+    "MOVE",
+    "MUL",
+    "MUX",
+    "REPLACE",
+    "RIGHT",
+    "ROL",
+    "ROR",
+    "SEL",
+    "SHL",
+    "SHR",
+    "SIN",
+    "SIZEOF",
+    "SQRT",
+    "SUB",
+    "TAN",
+    "TRUNC",
+    "TRUNC_INT",
+    "__DELETE",
+    "__ISVALIDREF",
+    "__NEW",
+    "__QUERYINTERFACE",
+    "__QUERYPOINTER",
+];
+
+/// The names declared across a project: global variable lists, types and POUs.
+#[derive(Debug, Default)]
+pub struct Names {
+    /// Global variable lists, by their names in upper case.
+    lists: BTreeMap<String, Scope>,
+    /// Types and POUs, by their names in upper case.
+    types: BTreeMap<String, Scope>,
+}
+
+/// The names declared by a global variable list, type or POU.
+#[derive(Debug, Default)]
+struct Scope {
+    name: String,
+    /// Whether members must be named through the scope, as with `{attribute 'qualified_only'}`.
+    qualified_only: bool,
+    enumeration: bool,
+    extends: Option<String>,
+    /// The type a function returns.
+    data_type: Option<DataType>,
+    /// Variables, enumeration values, methods and properties, with their types.
+    members: Vec<(String, Option<DataType>)>,
+}
+
+impl Names {
+    /// Collects the names declared by every Structured Text file under `dir`.
+    pub fn collect(dir: &Path) -> Result<Self> {
+        let names = RefCell::new(Self::default());
+        visit_dirs(dir, &|path| {
+            let file = structured_text::File::from_str(&fs::read_to_string(path)?)?;
+            let declarations: Vec<&str> = file
+                .into_iter()
+                .filter(|(content, _)| *content == Content::Declaration)
+                .map(|(_, text)| text)
+                .collect();
+            let file_name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            names.borrow_mut().add(file_name, &declarations);
+            Ok(())
+        })?;
+        Ok(names.into_inner())
+    }
+
+    /// Adds the declarations of one file, where a global variable list is named after the file.
+    pub fn add(&mut self, file_name: &str, declarations: &[&str]) {
+        let mut declarations = declarations
+            .iter()
+            .filter_map(|text| Declaration::from_str(text).ok());
+        let Some(first) = declarations.next() else {
+            return;
+        };
+
+        let mut scope = Scope {
+            name: file_name.to_string(),
+            qualified_only: first.attribute("qualified_only").is_some(),
+            enumeration: false,
+            extends: first.extends(),
+            data_type: first.data_type().cloned(),
+            members: first
+                .variables()
+                .into_iter()
+                .map(|variable| (variable.name.0, Some(variable.data_type)))
+                .collect(),
+        };
+        let Some(name) = first.name() else {
+            self.lists.insert(file_name.to_uppercase(), scope);
+            return;
+        };
+        scope.name = name.0.clone();
+
+        let enum_type = DataType::Flat(name.0.clone());
+        for member in first.enum_members() {
+            scope.enumeration = true;
+            scope.members.push((member.name.0, Some(enum_type.clone())));
+        }
+        for declaration in declarations {
+            if let Some(name) = declaration.name() {
+                scope
+                    .members
+                    .push((name.0.clone(), declaration.data_type().cloned()));
+            }
+        }
+        self.types.insert(scope.name.to_uppercase(), scope);
+    }
+
+    /// Finds `name` among the members of `scope` or of what it extends.
+    fn member<'a>(&'a self, scope: &'a Scope, name: &str) -> Found<'a> {
+        let mut scope = scope;
+        for _ in 0..self.types.len() + 1 {
+            if let Some((spelling, data_type)) = find(&scope.members, name) {
+                return Found::Value(spelling.clone(), data_type.clone());
+            }
+            scope = match &scope.extends {
+                None => return Found::Missing,
+                Some(extends) => match self.types.get(&extends.to_uppercase()) {
+                    Some(base) => base,
+                    None => return Found::Unknown,
+                },
+            };
+        }
+        Found::Unknown
+    }
+
+    /// The type `data_type` names, if it is declared in the project.
+    fn scope_of(&self, data_type: &DataType) -> Option<&Scope> {
+        match data_type {
+            DataType::Flat(name) => self.types.get(&name.to_uppercase()),
+            DataType::ReferenceTo(inner, _) => self.scope_of(inner),
+            _ => None,
+        }
+    }
+}
+
+fn find<'a>(
+    members: &'a [(String, Option<DataType>)],
+    name: &str,
+) -> Option<&'a (String, Option<DataType>)> {
+    members
+        .iter()
+        .find(|(member, _)| member.eq_ignore_ascii_case(name))
+}
+
+/// What a name was resolved to.
+enum Found<'a> {
+    /// A variable, member, method or property, as declared, with its type if known.
+    Value(String, Option<DataType>),
+    /// A global variable list, type or POU, whose members may follow.
+    Scope(&'a Scope),
+    /// Not declared where it was looked for.
+    Missing,
+    /// Declared differently by more than one global variable list or enumeration that can be used
+    /// unqualified.
+    Ambiguous,
+    /// Declared outside the project, such as in a library, so it cannot be checked.
+    Unknown,
+}
+
+impl Found<'_> {
+    fn data_type(&self) -> Option<&DataType> {
+        match self {
+            Self::Value(_, data_type) => data_type.as_ref(),
+            Self::Scope(scope) => scope.data_type.as_ref(),
+            Self::Missing | Self::Ambiguous | Self::Unknown => None,
+        }
+    }
+}
+
+/// Rewrites each identifier of an implementation with the casing of its declaration, and
+/// collects those it cannot resolve. `JMP` targets take the casing of their label. Members of
+/// types declared outside the project are left as written.
+pub struct Identifiers<'a> {
+    names: &'a Names,
+    owner: Option<String>,
+    extends: Option<String>,
+    /// The variables of the POU, method or property, and the return value of a function or
+    /// method.
+    locals: Vec<(String, Option<DataType>)>,
+    /// The jump labels of the implementation, as written where they mark a statement.
+    labels: Vec<String>,
+    pub unresolved: Vec<String>,
+    pub ambiguous: Vec<String>,
+}
+
+impl<'a> Identifiers<'a> {
+    /// Resolves against `names` and the declarations the implementation can see, the POU's first.
+    pub fn new(names: &'a Names, declarations: &[&str]) -> Self {
+        let declarations: Vec<Declaration> = declarations
+            .iter()
+            .filter_map(|text| Declaration::from_str(text).ok())
+            .collect();
+        let mut locals = Vec::new();
+        for declaration in &declarations {
+            if let (Some(name), Some(data_type)) = (declaration.name(), declaration.data_type()) {
+                locals.push((name.0.clone(), Some(data_type.clone())));
+            }
+            for variable in declaration.variables() {
+                locals.push((variable.name.0, Some(variable.data_type)));
+            }
+        }
+        let first = declarations.first();
+        Self {
+            names,
+            owner: first.and_then(Declaration::name).map(|name| name.0.clone()),
+            extends: first.and_then(Declaration::extends),
+            locals,
+            labels: Vec::new(),
+            unresolved: Vec::new(),
+            ambiguous: Vec::new(),
+        }
+    }
+
+    fn report(&mut self, found: &Found, name: &str) {
+        let names = match found {
+            Found::Ambiguous => &mut self.ambiguous,
+            _ => &mut self.unresolved,
+        };
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+
+    /// Finds the first name of a path such as `GVL.bEnable`.
+    fn root(&self, name: &str) -> Found<'a> {
+        let names = self.names;
+        if let Some((spelling, data_type)) = find(&self.locals, name) {
+            return Found::Value(spelling.clone(), data_type.clone());
+        }
+        let owner = self
+            .owner
+            .as_ref()
+            .and_then(|o| names.types.get(&o.to_uppercase()));
+        if let Some(owner) = owner {
+            if let found @ Found::Value(..) = names.member(owner, name) {
+                return found;
+            }
+        }
+        let pointer_to = |name: &Option<String>| {
+            name.as_ref().map(|name| {
+                DataType::PointerTo(Box::new(DataType::Flat(name.clone())), Spelling::default())
+            })
+        };
+        if name.eq_ignore_ascii_case("THIS") {
+            return Found::Value(name.to_string(), pointer_to(&self.owner));
+        }
+        if name.eq_ignore_ascii_case("SUPER") {
+            return Found::Value(name.to_string(), pointer_to(&self.extends));
+        }
+        let upper = name.to_uppercase();
+        if let Some(scope) = names.lists.get(&upper).or_else(|| names.types.get(&upper)) {
+            return Found::Scope(scope);
+        }
+        let unqualified = names
+            .lists
+            .values()
+            .chain(names.types.values().filter(|scope| scope.enumeration));
+        let mut matches = unqualified
+            .filter(|scope| !scope.qualified_only)
+            .filter_map(|scope| find(&scope.members, name));
+        if let Some(first @ (spelling, data_type)) = matches.next() {
+            if matches.any(|other| other != first) {
+                return Found::Ambiguous;
+            }
+            return Found::Value(spelling.clone(), data_type.clone());
+        }
+        if STANDARD_FUNCTIONS.contains(&upper.as_str())
+            || upper.starts_with("TO_")
+            || upper.contains("_TO_")
+        {
+            return Found::Unknown;
+        }
+        Found::Missing
+    }
+
+    /// Finds `name` as a member of what `found` resolved to.
+    fn member(&self, found: &Found<'a>, name: &str) -> Found<'a> {
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Found::Unknown;
+        }
+        let scope = match found {
+            Found::Scope(scope) => Some(*scope),
+            Found::Value(_, Some(data_type)) => self.names.scope_of(data_type),
+            _ => None,
+        };
+        match scope {
+            Some(scope) => self.names.member(scope, name),
+            None => Found::Unknown,
+        }
+    }
+
+    /// Rewrites a path such as `GVL.stAxis.fPosition` or `E_State#Idle` segment by segment.
+    fn resolve_path(&mut self, path: &mut String) -> Found<'a> {
+        let mut output = String::new();
+        let mut found = Found::Unknown;
+        let mut rest = path.as_str();
+        let mut first = true;
+        while !rest.is_empty() {
+            let end = rest.find(['.', '#']).unwrap_or(rest.len());
+            let name = &rest[..end];
+            found = if first {
+                self.root(name)
+            } else {
+                self.member(&found, name)
+            };
+            first = false;
+            match &found {
+                Found::Value(spelling, _) => output.push_str(spelling),
+                Found::Scope(scope) => output.push_str(&scope.name),
+                Found::Missing | Found::Ambiguous => {
+                    let written = format!("{output}{name}");
+                    self.report(&found, &written);
+                    return Found::Unknown;
+                }
+                Found::Unknown => {
+                    output.push_str(rest);
+                    break;
+                }
+            }
+            output.push_str(&rest[end..(end + 1).min(rest.len())]);
+            rest = &rest[(end + 1).min(rest.len())..];
+        }
+        *path = output;
+        found
+    }
+
+    fn resolve_member(&mut self, found: &Found<'a>, name: &mut String) -> Found<'a> {
+        match self.member(found, name) {
+            Found::Value(spelling, data_type) => {
+                name.clone_from(&spelling);
+                Found::Value(spelling, data_type)
+            }
+            found @ (Found::Missing | Found::Ambiguous) => {
+                self.report(&found, name);
+                Found::Unknown
+            }
+            other => other,
+        }
+    }
+
+    fn resolve(&mut self, expression: &mut ExpressionInner) -> Found<'a> {
+        match expression {
+            ExpressionInner::Value(value) => match &mut value.0 {
+                ValueInner::Flat(path) => self.resolve_path(path),
+                _ => {
+                    visit::walk_value_mut(self, value);
+                    Found::Unknown
+                }
+            },
+            ExpressionInner::Field(inner, member) => {
+                let found = self.resolve(inner);
+                match member {
+                    Member::Named(identifier) => self.resolve_member(&found, &mut identifier.0),
+                    Member::Unnamed(_) => Found::Unknown,
+                }
+            }
+            ExpressionInner::Index(inner, indices) => {
+                let found = self.resolve(inner);
+                for index in indices {
+                    self.resolve(index);
+                }
+                match found.data_type() {
+                    Some(DataType::Array(_, element, _)) => {
+                        Found::Value(String::new(), Some(*element.clone()))
+                    }
+                    _ => Found::Unknown,
+                }
+            }
+            ExpressionInner::Dereference(inner) => match self.resolve(inner).data_type() {
+                Some(DataType::PointerTo(target, _)) => {
+                    Found::Value(String::new(), Some(*target.clone()))
+                }
+                _ => Found::Unknown,
+            },
+            ExpressionInner::FunctionCall(call) => {
+                let callee = self.resolve(&mut call.identifier);
+                self.resolve_arguments(&callee, &mut call.arguments);
+                match callee {
+                    Found::Scope(scope) => Found::Value(String::new(), scope.data_type.clone()),
+                    _ => Found::Unknown,
+                }
+            }
+            ExpressionInner::Method(inner, call) => {
+                let found = self.resolve(inner);
+                let method = match &mut call.identifier {
+                    ExpressionInner::Value(value) => match &mut value.0 {
+                        ValueInner::Flat(name) => self.resolve_member(&found, name),
+                        _ => Found::Unknown,
+                    },
+                    identifier => self.resolve(identifier),
+                };
+                self.resolve_arguments(&Found::Unknown, &mut call.arguments);
+                match method.data_type() {
+                    Some(data_type) => Found::Value(String::new(), Some(data_type.clone())),
+                    None => Found::Unknown,
+                }
+            }
+            _ => {
+                visit::walk_expression_inner_mut(self, expression);
+                Found::Unknown
+            }
+        }
+    }
+
+    /// Resolves named arguments against the inputs and outputs of what is called.
+    fn resolve_arguments(&mut self, callee: &Found<'a>, arguments: &mut [(Argument, Vec<Ether>)]) {
+        let callee = match callee {
+            Found::Value(_, Some(data_type)) => match self.names.scope_of(data_type) {
+                Some(scope) => Found::Scope(scope),
+                None => Found::Unknown,
+            },
+            Found::Scope(scope) => Found::Scope(scope),
+            _ => Found::Unknown,
+        };
+        for (argument, _) in arguments {
+            let expression = match argument {
+                Argument::Unnamed(expression) => expression,
+                Argument::InputOrInout(identifier, expression)
+                | Argument::Output(identifier, expression) => {
+                    self.resolve_member(&callee, &mut identifier.0);
+                    expression
+                }
+            };
+            if let Some(expression) = expression {
+                self.resolve(&mut expression.0);
+            }
+        }
+    }
+}
+
+impl VisitMut for Identifiers<'_> {
+    fn visit_implementation_mut(&mut self, implementation: &mut Implementation) {
+        self.labels.clear();
+        collect_labels(&implementation.0, &mut self.labels);
+        visit::walk_implementation_mut(self, implementation);
+    }
+
+    fn visit_component_mut(&mut self, component: &mut C) {
+        if let C::Statement(_, items) = component {
+            let mut tokens = items
+                .iter_mut()
+                .filter(|item| !matches!(item, C::Ether(_) | C::Space));
+            if let (Some(C::Uppercase("JMP", _)), Some(C::Identifier(target))) =
+                (tokens.next(), tokens.next())
+            {
+                match self
+                    .labels
+                    .iter()
+                    .find(|l| l.eq_ignore_ascii_case(&target.0))
+                {
+                    Some(label) => target.0.clone_from(label),
+                    None => {
+                        let name = target.0.clone();
+                        self.report(&Found::Missing, &name);
+                    }
+                }
+            }
+        }
+        visit::walk_component_mut(self, component);
+    }
+
+    fn visit_expression_inner_mut(&mut self, expression: &mut ExpressionInner) {
+        self.resolve(expression);
+    }
+
+    fn visit_begin_middle_end_mut(&mut self, node: &mut BeginMiddleEnd) {
+        if node.keyword() == Some("FOR") {
+            let variable = node.begin.iter_mut().find_map(|component| match component {
+                C::Identifier(identifier) => Some(identifier),
+                _ => None,
+            });
+            if let Some(variable) = variable {
+                match self.root(&variable.0) {
+                    Found::Value(spelling, _) => variable.0 = spelling,
+                    found @ (Found::Missing | Found::Ambiguous) => {
+                        let name = variable.0.clone();
+                        self.report(&found, &name);
+                    }
+                    Found::Scope(_) | Found::Unknown => (),
+                }
+            }
+        }
+        visit::walk_begin_middle_end_mut(self, node);
+    }
+}
+
+/// Collects the names of statements such as `done:`, at any depth.
+fn collect_labels(components: &[C], labels: &mut Vec<String>) {
+    for component in components {
+        match component {
+            C::Statement(_, items) => {
+                let mut tokens = items.iter().filter(|item| !matches!(item, C::Ether(_)));
+                if let (Some(C::Identifier(label)), Some(C::Text(":"))) =
+                    (tokens.next(), tokens.next())
+                {
+                    if !labels.iter().any(|l| l.eq_ignore_ascii_case(&label.0)) {
+                        labels.push(label.0.clone());
+                    }
+                }
+                collect_labels(items, labels);
+            }
+            C::Repeat(inners) => collect_labels(inners, labels),
+            C::BeginMiddleEnd(block) => {
+                collect_labels(&block.begin, labels);
+                for middle in &block.middle {
+                    collect_labels(middle, labels);
+                }
+                collect_labels(&block.end, labels);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./test_identifiers.rs"]
+mod test_identifiers;
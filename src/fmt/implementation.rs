@@ -1,19 +1,96 @@
 use std::io::Result;
 use std::str::FromStr;
 
+use crate::components::{BeginMiddleEnd, Component as C};
 use crate::dazzle::{self, Dazzle};
 use crate::implementation::Implementation;
+use crate::visit::{self, VisitMut};
 
-pub(super) fn align(input: &str) -> Result<String> {
-    let implementation = Implementation::from_str(input)?;
+use super::{Identifiers, NormalizeLiterals, Options};
 
-    let mut dazzler = dazzle::Dazzler::default();
+pub(super) fn align(input: &str, options: &Options) -> Result<String> {
+    align_with(input, options, None)
+}
+
+/// Formats an implementation, first rewriting its identifiers if `identifiers` is given.
+pub(super) fn align_with(
+    input: &str,
+    options: &Options,
+    identifiers: Option<&mut Identifiers>,
+) -> Result<String> {
+    let mut implementation = Implementation::from_str(input)?;
+    if let Some(identifiers) = identifiers {
+        identifiers.visit_implementation_mut(&mut implementation);
+    }
+    CaseArms.visit_implementation_mut(&mut implementation);
+    if options.normalize_literals {
+        NormalizeLiterals.visit_implementation_mut(&mut implementation);
+    }
+
+    let mut dazzler = dazzle::Dazzler {
+        reflow_comments: options.reflow_comments,
+        keyword_case: options.keyword_case,
+        ..Default::default()
+    };
     for c in &implementation.0 {
         c.dazzle(&mut dazzler);
     }
     Ok(dazzler.f)
 }
 
+/// Keeps CASE arms with a single simple statement on one line, with the colons of consecutive
+/// arms aligned.
+struct CaseArms;
+
+impl VisitMut for CaseArms {
+    fn visit_begin_middle_end_mut(&mut self, node: &mut BeginMiddleEnd) {
+        if node.keyword() == Some("CASE") {
+            let mut run = Vec::new();
+            for component in node.middle.iter_mut().flatten() {
+                match component {
+                    C::BeginMiddleEnd(arm) if arm.keyword().is_none() && is_single_line(arm) => {
+                        arm.inline = true;
+                        run.push(arm);
+                    }
+                    C::BeginMiddleEnd(_) => align_colons(&mut run),
+                    _ => (),
+                }
+            }
+            align_colons(&mut run);
+        }
+        visit::walk_begin_middle_end_mut(self, node);
+    }
+}
+
+fn is_single_line(arm: &BeginMiddleEnd) -> bool {
+    let mut statements = arm.middle.iter().flatten().filter_map(|c| match c {
+        C::Statement(_, items) => Some(items),
+        _ => None,
+    });
+    let simple = match (statements.next(), statements.next()) {
+        (Some(items), None) => !items.iter().any(|c| matches!(c, C::BeginMiddleEnd(_))),
+        _ => false,
+    };
+    simple && !matches!(arm.begin.last(), Some(C::Ether(_)))
+}
+
+fn align_colons(run: &mut Vec<&mut BeginMiddleEnd>) {
+    let width = |arm: &BeginMiddleEnd| {
+        let mut dazzler = dazzle::Dazzler::default();
+        if let Some(labels) = arm.begin.first() {
+            labels.dazzle(&mut dazzler);
+        }
+        dazzle::width(&dazzler.f)
+    };
+    let max_width = run.iter().map(|arm| width(arm)).max().unwrap_or(0);
+    for arm in run.drain(..) {
+        let filler = max_width - width(arm);
+        if filler > 0 {
+            arm.begin.insert(1, C::Filler(filler));
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "./test_implementation.rs"]
 mod test_implementation;
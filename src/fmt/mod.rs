@@ -1,43 +1,125 @@
 use std::fs;
-use std::io::Result;
+use std::io::{Error, Result};
 use std::path::Path;
 use std::str::FromStr;
 
-use super::{structured_text, visit_dirs};
+use crate::code::Malformed;
+use crate::components::{Literal, Spelling};
+use crate::visit::VisitMut;
+use crate::{structured_text, visit_dirs};
+
+pub use identifiers::{Identifiers, Names};
 
 mod declaration;
+mod identifiers;
 mod implementation;
 mod tab;
 mod trailing_whitespace;
 
-pub const LINE_LENGTH_LIMIT: u8 = 120;
+pub const LINE_LENGTH_LIMIT: usize = 120;
+
+/// Formatting choices beyond layout, all off by default.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Spell literals canonically, such as `T#1S` for `t#1S` or `16#FF` for `16#ff`.
+    pub normalize_literals: bool,
+    /// Refill prose `(* *)` comments to the line length limit. Comments that do not read as prose,
+    /// such as diagrams, tables and commented-out code, are left as written.
+    pub reflow_comments: bool,
+    /// How to write keywords, such as `IF`, `VAR_INPUT`, `AND`, `NOT`, `ARRAY`, `AT` and the
+    /// elementary types.
+    pub keyword_case: KeywordCase,
+    /// Write each identifier of an implementation as its declaration does, looking through the
+    /// POU, global variable lists and types of the project. Identifiers that cannot be resolved
+    /// are reported and left as written.
+    pub normalize_identifiers: bool,
+}
+
+/// How keywords are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+    /// As written in the source.
+    Preserve,
+}
+
+impl KeywordCase {
+    /// Spells `keyword`, given in upper case, having been written as `spelling`.
+    pub fn apply(&self, keyword: &str, spelling: &Spelling) -> String {
+        match self {
+            Self::Upper => keyword.to_string(),
+            Self::Lower => keyword.to_lowercase(),
+            Self::Preserve if spelling.0.eq_ignore_ascii_case(keyword) => spelling.0.clone(),
+            Self::Preserve => keyword.to_string(),
+        }
+    }
+}
 
 pub fn fmt() -> Result<()> {
-    visit_dirs(Path::new("."), fmt_file)?;
+    fmt_with(&Options::default())
+}
+
+pub fn fmt_with(options: &Options) -> Result<()> {
+    let names = if options.normalize_identifiers {
+        Names::collect(Path::new("."))?
+    } else {
+        Names::default()
+    };
+    visit_dirs(Path::new("."), &|path| fmt_file(path, options, &names))?;
     Ok(())
 }
 
-fn fmt_file(path: &Path) -> Result<()> {
+fn fmt_file(path: &Path, options: &Options, names: &Names) -> Result<()> {
     let file = fs::read_to_string(path)?;
 
     let mut structured_text = structured_text::File::from_str(&file)?;
 
     structured_text.for_each_chunk(trailing_whitespace::trim_end)?;
     structured_text.for_each_chunk(tab::replace_with_whitespace)?;
-    if structured_text
-        .for_each_declaration(declaration::align)
-        .is_err()
+    if let Err(error) =
+        structured_text.for_each_declaration(|text| declaration::align(text, options))
     {
-        println!("Failed to format {path:?} (declaration)");
+        report_failure(path, "declaration", &error);
     }
-    if structured_text
-        .for_each_implementation(implementation::align)
-        .is_err()
+    if let Err(error) =
+        structured_text.for_each_implementation_with_declarations(|text, declarations| {
+            if !options.normalize_identifiers {
+                return implementation::align(text, options);
+            }
+            let mut identifiers = Identifiers::new(names, declarations);
+            let output = implementation::align_with(text, options, Some(&mut identifiers))?;
+            for name in identifiers.unresolved {
+                println!("Cannot resolve `{name}` in {path:?}");
+            }
+            for name in identifiers.ambiguous {
+                println!("Ambiguous `{name}` in {path:?}");
+            }
+            Ok(output)
+        })
     {
-        println!("Failed to format {path:?} (implementation)");
+        report_failure(path, "implementation", &error);
     }
 
     fs::write(path, structured_text.to_string())?;
 
     Ok(())
 }
+
+/// Says why `part` of the file at `path` was left as written, when the reason is something the
+/// user can fix.
+fn report_failure(path: &Path, part: &str, error: &Error) {
+    match error.get_ref().and_then(|e| e.downcast_ref::<Malformed>()) {
+        Some(malformed) => println!("Failed to format {path:?} ({part}): {malformed}"),
+        None => println!("Failed to format {path:?} ({part})"),
+    }
+}
+
+struct NormalizeLiterals;
+
+impl VisitMut for NormalizeLiterals {
+    fn visit_literal_mut(&mut self, literal: &mut Literal) {
+        literal.normalize();
+    }
+}
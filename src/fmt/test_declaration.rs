@@ -1,4 +1,10 @@
 use super::*;
+use crate::components::{VarKind, VarQualifier};
+use crate::fmt::KeywordCase;
+
+fn align(input: &str) -> Result<String> {
+    super::align(input, &Options::default())
+}
 
 #[test]
 fn enumerator() {
@@ -308,3 +314,762 @@ END_VAR
 
     assert_eq!(align(&input).unwrap(), input);
 }
+
+#[test]
+fn var_section_qualifiers() {
+    let input = String::from(
+        "FUNCTION_BLOCK FB_Sections
+VAR_INPUT   constant
+    a : INT;
+END_VAR
+var_output RETAIN
+    b : INT;
+END_VAR
+VAR_TEMP
+    c : INT;
+END_VAR
+VAR_STAT persistent   RETAIN // kept
+    d : INT;
+END_VAR
+VAR_EXTERNAL
+    e : INT;
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "FUNCTION_BLOCK FB_Sections
+VAR_INPUT CONSTANT
+    a : INT;
+END_VAR
+VAR_OUTPUT RETAIN
+    b : INT;
+END_VAR
+VAR_TEMP
+    c : INT;
+END_VAR
+VAR_STAT RETAIN PERSISTENT // kept
+    d : INT;
+END_VAR
+VAR_EXTERNAL
+    e : INT;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+
+    let sections = Declaration::from_str(&input).unwrap().var_sections();
+    assert_eq!(sections.len(), 5);
+    assert_eq!(sections[3].kind, VarKind::Stat);
+    assert_eq!(
+        sections[3].qualifiers,
+        vec![VarQualifier::Retain, VarQualifier::Persistent]
+    );
+}
+
+#[test]
+fn global_variable_list_qualifiers() {
+    let input = String::from(
+        "VAR_GLOBAL RETAIN PERSISTENT
+    a : INT;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn function_block_modifiers() {
+    let input = String::from(
+        "FUNCTION_BLOCK   abstract FB_Base IMPLEMENTS I_A,I_B
+VAR
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "FUNCTION_BLOCK ABSTRACT FB_Base IMPLEMENTS I_A, I_B
+VAR
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+
+    for input in [
+        "FUNCTION_BLOCK FINAL FB_Leaf EXTENDS FB_Base IMPLEMENTS Lib.I_A\n",
+        "FUNCTION_BLOCK INTERNAL FB_Hidden\n",
+        "FUNCTION_BLOCK PUBLIC FINAL FB_Open\n",
+        "METHOD PUBLIC ABSTRACT Run : BOOL\n",
+        "METHOD FINAL Stop\n",
+        "METHOD ABSTRACT PROTECTED Run\n",
+    ] {
+        assert_eq!(align(input).unwrap(), input);
+    }
+
+    for input in [
+        "FUNCTION_BLOCK PUBLIC PUBLIC FB_Twice\n",
+        "FUNCTION_BLOCK ABSTRACT FINAL FB_Both\n",
+        "FUNCTION_BLOCK PUBLIC INTERNAL FB_Both\n",
+        "METHOD PRIVATE PUBLIC Run\n",
+        "METHOD FINAL ABSTRACT Run\n",
+    ] {
+        assert!(align(input).is_err(), "{input}");
+    }
+}
+
+#[test]
+fn function_block_implements_wraps() {
+    let input = String::from(
+        "FUNCTION_BLOCK FB_Everything IMPLEMENTS I_FirstInterface, I_SecondInterface, I_ThirdInterface, I_FourthInterface, I_Fifth
+",
+    );
+
+    let output = String::from(
+        "FUNCTION_BLOCK FB_Everything IMPLEMENTS I_FirstInterface, I_SecondInterface, I_ThirdInterface, I_FourthInterface,
+    I_Fifth
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn alias_and_subrange() {
+    let input = String::from(
+        "TYPE T_Percent:INT (0 .. 100) := 50 ;
+END_TYPE
+",
+    );
+
+    let output = String::from(
+        "TYPE T_Percent : INT(0..100) := 50;
+END_TYPE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+
+    for input in [
+        "TYPE T_Name : STRING(80);\nEND_TYPE\n",
+        "{attribute 'qualified_only'}\nTYPE T_Speed : LREAL;\nEND_TYPE\n",
+    ] {
+        assert_eq!(align(input).unwrap(), input);
+    }
+}
+
+#[test]
+fn subrange_variable() {
+    let input = String::from(
+        "PROGRAM Main
+VAR
+    n : INT(0..10);
+    large : DINT(-1000..1000) := 5;
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "PROGRAM Main
+VAR
+    n     : INT(0..10);
+    large : DINT(-1000..1000) := 5;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn enumerator_base_type() {
+    let input = String::from(
+        "TYPE E_Mode :
+(
+    Idle := 0, // nothing to do
+    Mask := 16#FF,// all bits
+    Next := Mask + 1,
+    Last // end
+)   UDINT ;
+END_TYPE
+",
+    );
+
+    let output = String::from(
+        "TYPE E_Mode :
+(
+    Idle := 0,     // nothing to do
+    Mask := 16#FF, // all bits
+    Next := Mask + 1,
+    Last           // end
+) UDINT;
+END_TYPE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn enumerator_resolved_values() {
+    let input = "TYPE E_Mode :
+(
+    Idle, // zero
+    Run,
+    Mask := 16#FF,
+    Next := Mask + 1,
+    Big := UDINT#2#1000_0000,
+    After,
+    Unknown := SomeConstant,
+    Lost
+) UDINT;
+END_TYPE
+";
+
+    let members = Declaration::from_str(input).unwrap().enum_members();
+
+    assert_eq!(
+        members
+            .iter()
+            .map(|member| (member.name.0.as_str(), member.resolved))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Idle", Some(0)),
+            ("Run", Some(1)),
+            ("Mask", Some(255)),
+            ("Next", Some(256)),
+            ("Big", Some(128)),
+            ("After", Some(129)),
+            ("Unknown", None),
+            ("Lost", None),
+        ]
+    );
+}
+
+#[test]
+fn direct_addresses() {
+    let input = String::from(
+        "PROGRAM Main
+VAR
+    bStart at %ix0.1 : BOOL;
+    nSpeed AT %QW10 : INT;
+    fValue AT %MD4 : REAL;
+    nAny AT %I* : BYTE;
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "PROGRAM Main
+VAR
+    bStart AT %IX0.1 : BOOL;
+    nSpeed AT %QW10  : INT;
+    fValue AT %MD4   : REAL;
+    nAny   AT %I*    : BYTE;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn array_dimensions() {
+    let input = String::from(
+        "PROGRAM Main
+VAR
+    aGrid : ARRAY[0..1,0..2] OF INT;
+    aAxes : array [0..GVL_Const.MAX_AXES-1] of FB_Axis;
+    aAny : ARRAY[*,*] OF REAL;
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "PROGRAM Main
+VAR
+    aGrid : ARRAY [0..1, 0..2] OF INT;
+    aAxes : ARRAY [0..GVL_Const.MAX_AXES - 1] OF FB_Axis;
+    aAny  : ARRAY [*, *] OF REAL;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn strings_and_elementary_types() {
+    let input = String::from(
+        "PROGRAM Main
+VAR
+    sName : wstring[255] := \"Axis\";
+    sLabel : STRING(GVL.MAX_LEN);
+    tDelay : time;
+    tdNow : time_of_day;
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "PROGRAM Main
+VAR
+    sName  : WSTRING(255) := \"Axis\";
+    sLabel : STRING(GVL.MAX_LEN);
+    tDelay : TIME;
+    tdNow  : TIME_OF_DAY;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn array_initializers() {
+    let input = String::from(
+        "PROGRAM P
+VAR
+    a : ARRAY [1..15] OF INT := [10(0),5( 1 )];
+    b : ARRAY [1..2] OF ST_X := [(x := 1), (x := 2)];
+    c : ARRAY [1..5] OF STRING := ['first element', 'second element', 'third element', 'fourth element', 'fifth element'];
+    d : ARRAY [1..3] OF INT := [
+        1, // one
+        22, // two
+        333 // three
+    ];
+    e : ARRAY [1..2] OF ST_Long := [(abc := 'abc', then_a_number := 123, xyz := 'xyz', then_a_constant := MY_CONSTANT, last_of_all := 'the very last element'), 2()];
+END_VAR
+",
+    );
+    let output = String::from(
+        "PROGRAM P
+VAR
+    a : ARRAY [1..15] OF INT    := [10(0), 5(1)];
+    b : ARRAY [1..2] OF ST_X    := [(x := 1), (x := 2)];
+    c : ARRAY [1..5] OF STRING  := [
+        'first element',
+        'second element',
+        'third element',
+        'fourth element',
+        'fifth element'
+    ];
+    d : ARRAY [1..3] OF INT     := [
+        1,  // one
+        22, // two
+        333 // three
+    ];
+    e : ARRAY [1..2] OF ST_Long := [
+        (
+            abc             := 'abc',
+            then_a_number   := 123,
+            xyz             := 'xyz',
+            then_a_constant := MY_CONSTANT,
+            last_of_all     := 'the very last element'
+        ),
+        2()
+    ];
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn array_of_repeated_structures() {
+    let input = String::from(
+        "PROGRAM P
+VAR
+    a : ARRAY [1..2] OF ST_Point := [(x := 1111111111, y := 2222222222, z := 33333333333), (x := 1111111111, y := 2222222222, z := 33333333333)];
+END_VAR
+",
+    );
+    let output = String::from(
+        "PROGRAM P
+VAR
+    a : ARRAY [1..2] OF ST_Point := [
+        (x := 1111111111, y := 2222222222, z := 33333333333),
+        (x := 1111111111, y := 2222222222, z := 33333333333)
+    ];
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn function_block_instantiation() {
+    let input = String::from(
+        "PROGRAM P
+VAR
+    fbAxis : FB_Axis( nAxisId:=3,sName := 'X' ); // first axis
+    fbTon : TON := (PT := T#1S);
+    fbLogger : FB_Logger(sPath := 'C:/logs/machine', nMaxFiles := 10, eLevel := E_Level.Warning, bAppend := TRUE, nBufferSize := 4096);
+    fbEmpty : FB_Empty();
+END_VAR
+",
+    );
+    let output = String::from(
+        "PROGRAM P
+VAR
+    fbAxis   : FB_Axis(nAxisId := 3, sName := 'X'); // first axis
+    fbTon    : TON := (PT := T#1S);
+    fbLogger : FB_Logger(
+        sPath       := 'C:/logs/machine',
+        nMaxFiles   := 10,
+        eLevel      := E_Level.Warning,
+        bAppend     := TRUE,
+        nBufferSize := 4096,
+    );
+    fbEmpty  : FB_Empty();
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn conditional_pragmas() {
+    let input = String::from(
+        "FUNCTION_BLOCK FB
+VAR
+{IF defined(pou: MC_Power)}
+    fbPower : MC_Power;
+{ELSIF hasvalue (AXES, '2')}
+    fbPower : FB_PowerPair; // pair
+{ELSE}
+        fbPower : FB_Stub; // stub
+{END_IF}
+    x : INT;
+END_VAR
+{IF defined (SIMULATION)}
+VAR
+    fbSimulator : FB_Simulator;
+END_VAR
+{END_IF}
+",
+    );
+    let output = String::from(
+        "FUNCTION_BLOCK FB
+VAR
+    {IF defined (pou:MC_Power)}
+        fbPower     : MC_Power;
+    {ELSIF hasvalue (AXES, '2')}
+        fbPower     : FB_PowerPair; // pair
+    {ELSE}
+        fbPower     : FB_Stub;      // stub
+    {END_IF}
+    x           : INT;
+END_VAR
+{IF defined (SIMULATION)}
+    VAR
+        fbSimulator : FB_Simulator;
+    END_VAR
+{END_IF}
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn attributes() {
+    let input = "{attribute 'qualified_only'}
+{attribute 'strict'}
+FUNCTION_BLOCK FB_Io
+VAR_INPUT
+    {attribute 'TcLinkTo' := 'TIIB[EL1008]^Channel 1^Input'}
+    bSensor, bSpare : BOOL;
+    nCount : INT;
+END_VAR
+{attribute 'OPC.UA.DA' := '1'}
+VAR
+    {attribute 'hide'}
+    {attribute 'OPC.UA.DA' := '0'}
+    nHidden : INT;
+END_VAR
+";
+
+    assert_eq!(
+        align(input).unwrap(),
+        input
+            .replace("nCount :", "nCount          :")
+            .replace("nHidden :", "nHidden         :")
+    );
+
+    let declaration = Declaration::from_str(input).unwrap();
+    assert_eq!(
+        declaration
+            .attributes()
+            .iter()
+            .map(|attribute| attribute.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["qualified_only", "strict"]
+    );
+
+    let blocks = declaration.var_blocks();
+    assert_eq!(blocks.len(), 2);
+    assert!(blocks[0].attributes.is_empty());
+    assert_eq!(
+        blocks[1].attribute("opc.ua.da").unwrap().value.as_deref(),
+        Some("1")
+    );
+    assert_eq!(blocks[1].variables[0].name.0, "nHidden");
+
+    let linked = declaration.variables_with_attribute("TcLinkTo");
+    assert_eq!(
+        linked
+            .iter()
+            .map(|variable| variable.name.0.as_str())
+            .collect::<Vec<_>>(),
+        vec!["bSensor", "bSpare"]
+    );
+    assert_eq!(linked[0].section.as_ref().unwrap().kind, VarKind::Input);
+
+    let hidden = declaration.variables_with_attribute("hide");
+    assert_eq!(hidden.len(), 1);
+    assert_eq!(hidden[0].attributes.len(), 2);
+}
+
+#[test]
+fn structure_attributes() {
+    let input = "{attribute 'pack_mode' := '1'}
+TYPE ST_Packed :
+STRUCT
+    {attribute 'hide'}
+    nA : BYTE;
+    nB : DWORD;
+END_STRUCT
+END_TYPE
+";
+    let declaration = Declaration::from_str(input).unwrap();
+
+    assert_eq!(declaration.attributes()[0].value.as_deref(), Some("1"));
+    let variables = declaration.variables();
+    assert_eq!(variables.len(), 2);
+    assert_eq!(variables[0].section, None);
+    assert!(variables[0].attribute("hide").is_some());
+    assert!(variables[1].attributes.is_empty());
+}
+
+#[test]
+fn doc_comments() {
+    let input = "// Moves one axis.
+// Stops on any error.
+FUNCTION_BLOCK FB_Move
+VAR_INPUT // Commands
+    // Target position
+    // in millimetres
+    fTarget : LREAL;
+    bExecute : BOOL; // Starts on a rising edge
+
+    // Separated from nothing by a blank line
+
+    fVelocity : LREAL;
+    (* Maximum acceleration *)
+    {attribute 'hide'}
+    fAcceleration, fDeceleration : LREAL; (* both ways *) // second
+END_VAR
+";
+    let declaration = Declaration::from_str(input).unwrap();
+
+    assert_eq!(
+        declaration.doc().as_deref(),
+        Some("Moves one axis.\nStops on any error.")
+    );
+
+    let blocks = declaration.var_blocks();
+    assert_eq!(blocks[0].doc.as_deref(), Some("Commands"));
+
+    let docs: Vec<(String, Option<String>)> = declaration
+        .variables()
+        .into_iter()
+        .map(|variable| (variable.name.0, variable.doc))
+        .collect();
+    assert_eq!(
+        docs,
+        vec![
+            (
+                String::from("fTarget"),
+                Some(String::from("Target position\nin millimetres"))
+            ),
+            (
+                String::from("bExecute"),
+                Some(String::from("Starts on a rising edge"))
+            ),
+            (String::from("fVelocity"), None),
+            (
+                String::from("fAcceleration"),
+                Some(String::from("Maximum acceleration\nboth ways"))
+            ),
+            (
+                String::from("fDeceleration"),
+                Some(String::from("Maximum acceleration\nboth ways"))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn doc_comments_method_and_structure() {
+    let method = Declaration::from_str(
+        "(* Resets the axis
+   and clears errors. *)
+METHOD PUBLIC Reset : BOOL
+",
+    )
+    .unwrap();
+    assert_eq!(
+        method.doc().as_deref(),
+        Some("Resets the axis\n   and clears errors.")
+    );
+
+    let structure = Declaration::from_str(
+        "// A point
+TYPE ST_Point :
+STRUCT
+    x : REAL; // Horizontal
+    // Vertical
+    y : REAL;
+END_STRUCT
+END_TYPE
+",
+    )
+    .unwrap();
+    assert_eq!(structure.doc().as_deref(), Some("A point"));
+    let variables = structure.variables();
+    assert_eq!(variables[0].doc.as_deref(), Some("Horizontal"));
+    assert_eq!(variables[1].doc.as_deref(), Some("Vertical"));
+}
+
+#[test]
+fn block_comments() {
+    let input = String::from(
+        "FUNCTION_BLOCK FB_Valve
+VAR
+        (* Wiring:
+             +24V --[ K1 ]-- Y1
+        *)
+    bOpen : BOOL;
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "FUNCTION_BLOCK FB_Valve
+VAR
+    (* Wiring:
+         +24V --[ K1 ]-- Y1
+    *)
+    bOpen : BOOL;
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn keyword_case() {
+    let input = String::from(
+        "Function_Block FB_Case Extends FB_Base
+var_input Constant
+    a : INT := 1 + 2;
+    b : Array [0..1] Of Pointer To time_of_day;
+    c AT %IX0.1 : Bool;
+    d : Reference To wString(10);
+END_VAR
+",
+    );
+
+    let lower = String::from(
+        "function_block FB_Case extends FB_Base
+var_input constant
+    a           : int := 1 + 2;
+    b           : array [0..1] of pointer to time_of_day;
+    c at %IX0.1 : bool;
+    d           : reference to wstring(10);
+end_var
+",
+    );
+
+    let preserve = String::from(
+        "Function_Block FB_Case Extends FB_Base
+var_input Constant
+    a           : INT := 1 + 2;
+    b           : Array [0..1] Of Pointer To time_of_day;
+    c AT %IX0.1 : Bool;
+    d           : Reference To wString(10);
+END_VAR
+",
+    );
+
+    for (keyword_case, output) in [
+        (KeywordCase::Lower, lower),
+        (KeywordCase::Preserve, preserve),
+    ] {
+        let options = Options {
+            keyword_case,
+            ..Default::default()
+        };
+        assert_eq!(super::align(&input, &options).unwrap(), output);
+    }
+}
+
+#[test]
+fn non_ascii_width() {
+    let input = String::from(
+        "VAR_GLOBAL
+    sUnit : STRING := '°C'; // Temperatur
+    sName : STRING := 'Kühler'; // Gerät
+    nCount : INT := 1; // Zähler
+    stDefault : ST_Text := (sShort := 'ä', // erste
+        sLonger := 'abc'); // zweite
+END_VAR
+",
+    );
+
+    let output = String::from(
+        "VAR_GLOBAL
+    sUnit     : STRING  := '°C';     // Temperatur
+    sName     : STRING  := 'Kühler'; // Gerät
+    nCount    : INT     := 1;        // Zähler
+    stDefault : ST_Text :=
+        (
+            sShort  := 'ä', // erste
+            sLonger := 'abc'
+        );                           // zweite
+END_VAR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn malformed_literals() {
+    let input = "PROGRAM P
+VAR
+    n : WORD := 16#GG;
+END_VAR
+";
+    assert_eq!(
+        align(input).unwrap_err().to_string(),
+        "Malformed literal `16#GG`: `G` is not a base 16 digit"
+    );
+
+    let input = "PROGRAM P
+VAR
+    s : STRING := 'a$Qb';
+END_VAR
+";
+    assert_eq!(
+        align(input).unwrap_err().to_string(),
+        "Malformed string literal 'a$Qb': unknown escape `$Q` at character 2"
+    );
+}
@@ -0,0 +1,243 @@
+use super::*;
+use crate::fmt::{implementation, Options};
+
+fn names() -> Names {
+    let mut names = Names::default();
+    names.add(
+        "GVL_Main",
+        &["VAR_GLOBAL
+    bStart : BOOL;
+END_VAR
+"],
+    );
+    names.add(
+        "GVL_Io",
+        &["{attribute 'qualified_only'}
+VAR_GLOBAL
+    nCount : INT;
+END_VAR
+"],
+    );
+    names.add(
+        "E_State",
+        &["{attribute 'qualified_only'}
+TYPE E_State :
+(
+    Idle,
+    Running
+);
+END_TYPE
+"],
+    );
+    names.add(
+        "E_Mode",
+        &["TYPE E_Mode :
+(
+    Auto,
+    Manual
+);
+END_TYPE
+"],
+    );
+    names.add(
+        "ST_Axis",
+        &["TYPE ST_Axis :
+STRUCT
+    fPosition : LREAL;
+    bBusy : BOOL;
+END_STRUCT
+END_TYPE
+"],
+    );
+    names.add(
+        "F_Double",
+        &["FUNCTION F_Double : INT
+VAR_INPUT
+    nValue : INT;
+END_VAR
+"],
+    );
+    names.add(
+        "FB_Base",
+        &["FUNCTION_BLOCK FB_Base
+VAR_INPUT
+    bEnable : BOOL;
+END_VAR
+"],
+    );
+    names.add("FB_Machine", &[MACHINE, "METHOD M_Reset : BOOL\n"]);
+    names
+}
+
+const MACHINE: &str = "FUNCTION_BLOCK FB_Machine EXTENDS FB_Base
+VAR
+    stAxis : ST_Axis;
+    aAxes : ARRAY [1..2] OF ST_Axis;
+    pAxis : POINTER TO ST_Axis;
+    eState : E_State;
+    eMode : E_Mode;
+    fbTimer : TON;
+    i : INT;
+END_VAR
+";
+
+fn normalize(names: &Names, declarations: &[&str], input: &str) -> (String, Vec<String>) {
+    let mut identifiers = Identifiers::new(names, declarations);
+    let output =
+        implementation::align_with(input, &Options::default(), Some(&mut identifiers)).unwrap();
+    (output, identifiers.unresolved)
+}
+
+#[test]
+fn declared_casing() {
+    let input = "IF BENABLE AND gvl_main.BSTART THEN
+    STAXIS.FPOSITION := 0;
+    aaxes[1].bbusy := TRUE;
+    estate := e_state.RUNNING;
+    emode := AUTO;
+    fbtimer(IN := TRUE, PT := T#1S);
+    gvl_io.ncount := f_double(NVALUE := GVL_IO.NCOUNT);
+    FOR I := 1 TO 2 DO
+        aAxes[i].FPOSITION := paxis^.fposition + ABS(-1.0);
+    END_FOR
+    this^.m_reset();
+END_IF
+";
+
+    let output = "IF bEnable AND GVL_Main.bStart THEN
+    stAxis.fPosition := 0;
+    aAxes[1].bBusy := TRUE;
+    eState := E_State.Running;
+    eMode := Auto;
+    fbTimer(IN := TRUE, PT := T#1S);
+    GVL_Io.nCount := F_Double(nValue := GVL_Io.nCount);
+    FOR i := 1 TO 2 DO
+        aAxes[i].fPosition := pAxis^.fPosition + ABS(-1.0);
+    END_FOR
+    this^.M_Reset();
+END_IF
+";
+
+    assert_eq!(
+        normalize(&names(), &[MACHINE], input),
+        (output.to_string(), Vec::new())
+    );
+}
+
+#[test]
+fn unresolved() {
+    let input = "bunknown := stAxis.BMISSING OR ncount OR running;
+fbTimer.q := estate = E_STATE.STOPPED;
+";
+
+    let output = "bunknown := stAxis.BMISSING OR ncount OR running;
+fbTimer.q := eState = E_STATE.STOPPED;
+";
+
+    assert_eq!(
+        normalize(&names(), &[MACHINE], input),
+        (
+            output.to_string(),
+            vec![
+                String::from("bunknown"),
+                String::from("stAxis.BMISSING"),
+                String::from("ncount"),
+                String::from("running"),
+                String::from("E_State.STOPPED"),
+            ]
+        )
+    );
+}
+
+#[test]
+fn method_locals() {
+    let method = "METHOD M_Move : BOOL
+VAR_INPUT
+    fTarget : LREAL;
+END_VAR
+";
+    let input = "stAxis.fPosition := FTARGET;
+m_move := TRUE;
+";
+
+    let output = "stAxis.fPosition := fTarget;
+M_Move := TRUE;
+";
+
+    assert_eq!(
+        normalize(&names(), &[MACHINE, method], input),
+        (output.to_string(), Vec::new())
+    );
+}
+
+#[test]
+fn ambiguous() {
+    let mut names = names();
+    names.add(
+        "E_Drive",
+        &["TYPE E_Drive :
+(
+    AUTO,
+    Off
+);
+END_TYPE
+"],
+    );
+    names.add(
+        "GVL_Other",
+        &["{attribute 'Qualified_Only'}
+VAR_GLOBAL
+    nOther : INT;
+END_VAR
+"],
+    );
+
+    let input = "emode := auto;
+x := noff OR OFF OR nother;
+";
+
+    let output = "eMode := auto;
+x := noff OR Off OR nother;
+";
+
+    let mut identifiers = Identifiers::new(&names, &[MACHINE]);
+    let formatted =
+        implementation::align_with(input, &Options::default(), Some(&mut identifiers)).unwrap();
+    assert_eq!(formatted, output);
+    assert_eq!(identifiers.ambiguous, vec![String::from("auto")]);
+    assert_eq!(
+        identifiers.unresolved,
+        vec![
+            String::from("x"),
+            String::from("noff"),
+            String::from("nother")
+        ]
+    );
+}
+
+#[test]
+fn jump_labels() {
+    let input = "JMP RETRY;
+IF stAxis.bBusy THEN
+    JMP Finished;
+END_IF
+retry:
+JMP done;
+";
+
+    let output = "JMP retry;
+IF stAxis.bBusy THEN
+    JMP Finished;
+END_IF
+retry:
+JMP done;
+";
+
+    assert_eq!(
+        normalize(&names(), &[MACHINE], input),
+        (
+            output.to_string(),
+            vec![String::from("Finished"), String::from("done")]
+        )
+    );
+}
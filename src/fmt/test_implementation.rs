@@ -1,4 +1,9 @@
 use super::*;
+use crate::fmt::KeywordCase;
+
+fn align(input: &str) -> Result<String> {
+    super::align(input, &Options::default())
+}
 
 #[test]
 fn underscores() {
@@ -363,3 +368,397 @@ END_FOR
 
     assert_eq!(align(&input).unwrap(), output);
 }
+
+#[test]
+fn continue_jump_and_label() {
+    let input = String::from(
+        "FOR i := 0 TO 10 DO
+    IF skip[i] THEN
+        continue;
+    END_IF
+    jmp   retry  ;
+END_FOR
+retry:
+x := 1;
+",
+    );
+
+    let output = String::from(
+        "FOR i := 0 TO 10 DO
+    IF skip[i] THEN
+        CONTINUE;
+    END_IF
+    JMP retry;
+END_FOR
+retry:
+x := 1;
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn empty_bodies() {
+    let input = String::from(
+        "IF x THEN
+    // nothing to do yet
+END_IF
+FOR i := 0 TO 1 DO
+END_FOR
+WHILE x DO (* wait *)
+END_WHILE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn case_labels_are_not_jump_labels() {
+    let input = String::from(
+        "CASE state OF
+    Idle :
+        state := Busy;
+    Busy :
+        state := Idle;
+END_CASE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn case_jump_labels() {
+    let input = String::from(
+        "CASE x OF
+    1:
+        lbl:
+        y := 1;
+        JMP LBL;
+    2:
+        JMP again;
+        again :
+        y := 2;
+    done:
+        y := 3;
+END_CASE
+",
+    );
+
+    let output = String::from(
+        "CASE x OF
+    1 :
+        lbl:
+        y := 1;
+        JMP LBL;
+    2 :
+        JMP again;
+        again:
+        y := 2;
+    done :
+        y := 3;
+END_CASE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn case_single_line_arms() {
+    let input = String::from(
+        "CASE state OF
+    1:x := 1;
+    1,2, 5..7 : x := 2; // several
+    E_State.Idle, E_State.Stop: x := 3;
+    10 :
+        x := 4;
+    ELSE
+        x := 0;
+END_CASE
+",
+    );
+
+    let output = String::from(
+        "CASE state OF
+    1                          : x := 1;
+    1, 2, 5..7                 : x := 2; // several
+    E_State.Idle, E_State.Stop : x := 3;
+    10 :
+        x := 4;
+    ELSE
+        x := 0;
+END_CASE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn case_long_arm_after_short_arm() {
+    let input = String::from(
+        "CASE state OF
+    1 : y := 1;
+    2 : y := nTerm01 + nTerm02 + nTerm03 + nTerm04 + nTerm05 + nTerm06 + nTerm07 + nTerm08 + nTerm09 + nTerm10;
+END_CASE
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn for_loop_expression_step() {
+    let input = String::from(
+        "FOR i := n (* last *) TO 0 BY -1 DO
+    x := i;
+END_FOR
+FOR i := 0 TO n BY nStep * 2 // doubled
+DO
+    x := i;
+END_FOR
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn normalize_literals() {
+    let input = String::from(
+        "timer(IN := true, PT := t#1S);
+mask := 16#ff AND word#2#1010;
+",
+    );
+
+    let output = String::from(
+        "timer(IN := TRUE, PT := T#1S);
+mask := 16#FF AND WORD#2#1010;
+",
+    );
+
+    let options = Options {
+        normalize_literals: true,
+        ..Default::default()
+    };
+    assert_eq!(super::align(&input, &options).unwrap(), output);
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn typed_enumeration_values() {
+    let input = String::from(
+        "IF state = E_Mode#Auto THEN
+    state := E_Mode#Manual;
+END_IF
+",
+    );
+
+    let options = Options {
+        normalize_literals: true,
+        ..Default::default()
+    };
+    assert_eq!(super::align(&input, &options).unwrap(), input);
+    assert_eq!(align(&input).unwrap(), input);
+}
+
+#[test]
+fn conditional_pragmas() {
+    let input = String::from(
+        "IF a THEN
+{IF defined (SIMULATION)}
+    b := 1;
+{ELSE}
+    b := 2;
+    {END_IF}
+END_IF
+{IF defined (X)}
+c := 1;
+{END_IF}
+",
+    );
+    let output = String::from(
+        "IF a THEN
+    {IF defined (SIMULATION)}
+        b := 1;
+    {ELSE}
+        b := 2;
+    {END_IF}
+END_IF
+{IF defined (X)}
+    c := 1;
+{END_IF}
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+}
+
+#[test]
+fn block_comments() {
+    let input = String::from(
+        "IF bEnable THEN
+(* State machine:
+     IDLE --> RUNNING
+       |         |
+       +--> ERROR <+
+*)
+nState := 1;
+        (*
+        nOld := nState;
+            nState := 0;
+        *)
+END_IF
+",
+    );
+
+    let output = String::from(
+        "IF bEnable THEN
+    (* State machine:
+         IDLE --> RUNNING
+           |         |
+           +--> ERROR <+
+    *)
+    nState := 1;
+    (*
+    nOld := nState;
+        nState := 0;
+    *)
+END_IF
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), output);
+    assert_eq!(align(&output).unwrap(), output);
+}
+
+#[test]
+fn reflow_comments() {
+    let input = String::from(
+        "IF bEnable THEN
+    (* The axis is homed before it is moved,
+    so that the position is known.
+
+    Homing takes a few seconds. *)
+    nState := 1;
+    (* Table:
+       A    B
+       1    2 *)
+END_IF
+",
+    );
+
+    let output = String::from(
+        "IF bEnable THEN
+    (* The axis is homed before it is moved, so that the position is known.
+
+       Homing takes a few seconds. *)
+    nState := 1;
+    (* Table:
+       A    B
+       1    2 *)
+END_IF
+",
+    );
+
+    let options = Options {
+        reflow_comments: true,
+        ..Default::default()
+    };
+    assert_eq!(super::align(&input, &options).unwrap(), output);
+    assert_eq!(super::align(&output, &options).unwrap(), output);
+
+    let long = "(* ".to_string() + &"word ".repeat(30) + "*)\n";
+    let reflowed = super::align(&long, &options).unwrap();
+    assert_eq!(reflowed.lines().count(), 2);
+    assert!(reflowed.lines().all(|line| line.len() <= 120));
+    assert!(reflowed.lines().nth(1).unwrap().starts_with("   word"));
+}
+
+#[test]
+fn reflow_comments_not_prose() {
+    let options = Options {
+        reflow_comments: true,
+        ..Default::default()
+    };
+    for input in [
+        "(* table:\n   a | b\n   1 | 2 *)\n",
+        "(*x := 1;\ny := 2;*)\n",
+    ] {
+        assert_eq!(super::align(input, &options).unwrap(), input);
+    }
+}
+
+#[test]
+fn keyword_case() {
+    let input = String::from(
+        "if a and not b Then
+    x := y mod 2;
+ElsIf c OR_ELSE d then
+    z ref= w;
+end_if
+",
+    );
+
+    let upper = String::from(
+        "IF a AND NOT b THEN
+    x := y MOD 2;
+ELSIF c OR_ELSE d THEN
+    z REF= w;
+END_IF
+",
+    );
+
+    let lower = String::from(
+        "if a and not b then
+    x := y mod 2;
+elsif c or_else d then
+    z ref= w;
+end_if
+",
+    );
+
+    let preserve = String::from(
+        "if a and not b Then
+    x := y mod 2;
+ElsIf c OR_ELSE d then
+    z ref= w;
+end_if
+",
+    );
+
+    assert_eq!(align(&input).unwrap(), upper);
+    for (keyword_case, output) in [
+        (KeywordCase::Lower, lower),
+        (KeywordCase::Preserve, preserve),
+    ] {
+        let options = Options {
+            keyword_case,
+            ..Default::default()
+        };
+        assert_eq!(super::align(&input, &options).unwrap(), output);
+    }
+}
+
+#[test]
+fn malformed_literals() {
+    for (input, reason) in [
+        (
+            "x := 16#GG;\n",
+            "Malformed literal `16#GG`: `G` is not a base 16 digit",
+        ),
+        (
+            "IF a THEN\n    t := T#1x;\nEND_IF\n",
+            "Malformed literal `T#1x`: unknown duration unit `x`",
+        ),
+        (
+            "s := 'a$Qb';\n",
+            "Malformed string literal 'a$Qb': unknown escape `$Q` at character 2",
+        ),
+    ] {
+        assert_eq!(align(input).unwrap_err().to_string(), reason);
+    }
+}
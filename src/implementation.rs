@@ -1,13 +1,22 @@
 use std::io::{Error, ErrorKind, Result};
 use std::str::FromStr;
 
-use crate::code::Code;
-use crate::components::{Component as C, Ether};
+use crate::code::{Code, Span};
+use crate::components::{Component as C, Ether, Identifier};
 use crate::layout::{string_and_format_get_items, Layout as L};
+use crate::statement::{self, Statement};
 
 #[derive(Debug)]
 pub struct Implementation(pub Vec<C>);
 
+impl Implementation {
+    /// Lowers the components to statements. These are read-only: to change the output, edit or
+    /// visit the components instead.
+    pub fn statements(&self) -> Vec<Statement> {
+        statement::lower_all(&self.0)
+    }
+}
+
 impl FromStr for Implementation {
     type Err = Error;
     fn from_str(input: &str) -> Result<Self> {
@@ -17,20 +26,18 @@ impl FromStr for Implementation {
                 if code.end_of_file() {
                     Ok(Self(implementation))
                 } else {
-                    Err(Error::new(
-                        ErrorKind::InvalidData,
-                        format!("Cannot parse\n{code}"),
-                    ))
+                    Err(code.malformed().unwrap_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, format!("Cannot parse\n{code}"))
+                    }))
                 }
             }
             Err(_) => {
                 if code.trim_start().end_of_file() {
                     Ok(Self(Vec::new()))
                 } else {
-                    Err(Error::new(
-                        ErrorKind::InvalidData,
-                        format!("Cannot parse\n{code}"),
-                    ))
+                    Err(code.malformed().unwrap_or_else(|| {
+                        Error::new(ErrorKind::InvalidData, format!("Cannot parse\n{code}"))
+                    }))
                 }
             }
         }
@@ -38,28 +45,96 @@ impl FromStr for Implementation {
 }
 
 pub fn peel(code: &mut Code) -> Result<Vec<C>> {
+    peel_with_labels(code, true)
+}
+
+/// Peels the statements of a `CASE` arm, where `name :` begins the next arm unless a `JMP name`
+/// in the same arm makes it a jump target.
+pub fn peel_case_arm(code: &mut Code) -> Result<Vec<C>> {
+    peel_with_labels(code, false)
+}
+
+fn peel_with_labels(code: &mut Code, labels: bool) -> Result<Vec<C>> {
     let mut implementation = Vec::new();
     let mut code_clone = code.clone();
 
-    while let Ok(items) = string_get_implementation_items(&mut code_clone) {
-        implementation.extend(items);
+    loop {
+        let statement = match peel_statement(&mut code_clone, labels) {
+            Ok(statement) => statement,
+            Err(_) if !labels => match peel_jump_target(&mut code_clone, &implementation) {
+                Some(label) => label,
+                None => break,
+            },
+            Err(_) => break,
+        };
+        implementation.push(statement);
     }
     for ether in Ether::peel(&mut code_clone)? {
         implementation.push(C::Ether(ether));
     }
 
-    if implementation.is_empty() {
-        Err(Error::new(
-            ErrorKind::InvalidData,
-            format!("No implementation\n{code}"),
-        ))
-    } else {
+    *code = code_clone;
+    Ok(implementation)
+}
+
+fn peel_statement(code: &mut Code, labels: bool) -> Result<C> {
+    let mut code_clone = code.clone();
+    let mut items = Ether::peel(&mut code_clone)?
+        .into_iter()
+        .map(C::Ether)
+        .collect::<Vec<C>>();
+    let start = code_clone.position();
+    items.extend(string_get_implementation_items(&mut code_clone, labels)?);
+    let end = start + statement::trim_trivia_end(code_clone.since(start));
+    *code = code_clone;
+    Ok(C::Statement(Span { start, end }, items))
+}
+
+/// Peels `name :` inside a `CASE` arm when the arm, before or after it, jumps to `name`.
+fn peel_jump_target(code: &mut Code, before: &[C]) -> Option<C> {
+    let mut code_clone = code.clone();
+    let label = peel_statement(&mut code_clone, true).ok()?;
+    let C::Statement(_, ref items) = label else {
+        return None;
+    };
+    let name = items.iter().find_map(|item| match item {
+        C::Identifier(identifier) => Some(identifier),
+        _ => None,
+    })?;
+
+    let after = peel_case_arm(&mut code_clone.clone()).ok()?;
+    if jumps_to(before, name) || jumps_to(&after, name) {
         *code = code_clone;
-        Ok(implementation)
+        Some(label)
+    } else {
+        None
     }
 }
 
-fn string_get_implementation_items(code: &mut Code) -> Result<Vec<C>> {
+fn jumps_to(components: &[C], label: &Identifier) -> bool {
+    components
+        .iter()
+        .enumerate()
+        .any(|(i, component)| {
+            match component {
+        C::Uppercase("JMP", _) => components[i + 1..]
+            .iter()
+            .find(|c| !matches!(c, C::Space))
+            .is_some_and(|c| {
+                matches!(c, C::Identifier(target) if target.0.eq_ignore_ascii_case(&label.0))
+            }),
+        C::Statement(_, inner) | C::Repeat(inner) => jumps_to(inner, label),
+        C::BeginMiddleEnd(block) => {
+            jumps_to(&block.begin, label)
+                || block.middle.iter().any(|middle| jumps_to(middle, label))
+                || jumps_to(&block.end, label)
+        }
+        _ => false,
+    }
+        })
+}
+
+fn string_get_implementation_items(code: &mut Code, labels: bool) -> Result<Vec<C>> {
     if let Ok(output) =
         string_and_format_get_items(code, &[L::Uppercase("RETURN"), L::Text(";"), L::LineFeed])
     {
@@ -68,6 +143,12 @@ fn string_get_implementation_items(code: &mut Code) -> Result<Vec<C>> {
         string_and_format_get_items(code, &[L::Uppercase("EXIT"), L::Text(";"), L::LineFeed])
     {
         Ok(output)
+    } else if let Ok(output) =
+        string_and_format_get_items(code, &[L::Uppercase("CONTINUE"), L::Text(";"), L::LineFeed])
+    {
+        Ok(output)
+    } else if let Ok(output) = string_and_format_get_items(code, JUMP) {
+        Ok(output)
     } else if let Ok(output) = string_and_format_get_items(code, ASSIGNMENT) {
         Ok(output)
     } else if let Ok(output) = string_and_format_get_items(code, IF) {
@@ -86,6 +167,8 @@ fn string_get_implementation_items(code: &mut Code) -> Result<Vec<C>> {
         Ok(output)
     } else if let Ok(output) = string_and_format_get_items(code, &[L::Text(";"), L::LineFeed]) {
         Ok(output)
+    } else if labels {
+        string_and_format_get_items(code, LABEL)
     } else {
         Err(Error::new(
             ErrorKind::InvalidData,
@@ -94,6 +177,16 @@ fn string_get_implementation_items(code: &mut Code) -> Result<Vec<C>> {
     }
 }
 
+const JUMP: &[L] = &[
+    L::Uppercase("JMP"),
+    L::Space,
+    L::Identifier,
+    L::Text(";"),
+    L::LineFeed,
+];
+
+const LABEL: &[L] = &[L::Identifier, L::Text(":"), L::LineFeed];
+
 const ASSIGNMENT: &[L] = &[
     L::Expression,
     L::Space,
@@ -159,8 +252,8 @@ const CASE: &[L] = &[L::BeginMiddleEnd(
     ],
     &[
         L::BeginMiddleEnd(
-            &[L::Expression, L::Space, L::Text(":")],
-            &[L::Implementation],
+            &[L::CaseLabels, L::Space, L::Text(":")],
+            &[L::CaseImplementation],
             &[],
         ),
         L::Option(&[L::BeginMiddleEnd(
@@ -186,7 +279,7 @@ const FOR: &[L] = &[L::BeginMiddleEnd(
         L::Space,
         L::Expression,
         L::Space,
-        L::Option(&[L::Uppercase("BY"), L::Space, L::Value, L::Space]),
+        L::Option(&[L::Uppercase("BY"), L::Space, L::Expression, L::Space]),
         L::Uppercase("DO"),
     ],
     &[L::Implementation],
@@ -2,8 +2,8 @@ use std::io::{Error, ErrorKind, Result};
 
 use crate::code::Code;
 use crate::components::{
-    Address, BeginMiddleEnd, Component as C, DataType, Ether, Expression, Identifier,
-    IdentifierSub, Value,
+    Address, ArgumentList, BeginMiddleEnd, CaseLabels, Component as C, DataType, Ether, Expression,
+    Identifier, IdentifierList, IdentifierSub, Spelling, VarSection,
 };
 use crate::implementation;
 
@@ -37,16 +37,22 @@ fn string_and_one_format_get_items(code: &mut Code, layout: &Layout) -> Result<V
             }
         }
         Layout::Uppercase(text) => {
-            output.push(C::Uppercase(peel_uppercase(&mut code_clone, text)?))
+            let spelling = peel_uppercase(&mut code_clone, text)?;
+            output.push(C::Uppercase(text, spelling))
         }
         Layout::Text(text) => output.push(C::Text(peel(&mut code_clone, text)?)),
         Layout::Identifier => output.push(C::Identifier(Identifier::peel(&mut code_clone)?)),
         Layout::IdentifierSub => {
             output.push(C::IdentifierSub(IdentifierSub::peel(&mut code_clone)?))
         }
+        Layout::IdentifierList => {
+            output.push(C::IdentifierList(IdentifierList::peel(&mut code_clone)?))
+        }
         Layout::Address => output.push(C::Address(Address::peel(&mut code_clone)?)),
+        Layout::Arguments => output.push(C::Arguments(ArgumentList::peel(&mut code_clone)?)),
+        Layout::CaseLabels => output.push(C::CaseLabels(CaseLabels::peel(&mut code_clone)?)),
         Layout::DataType => output.push(C::DataType(DataType::peel(&mut code_clone)?)),
-        Layout::Value => output.push(C::Value(Value::peel(&mut code_clone)?)),
+        Layout::VarSection => output.push(C::VarSection(VarSection::peel(&mut code_clone)?)),
         Layout::Expression => output.push(C::Expression(Expression::peel(&mut code_clone)?)),
         Layout::OneOf(options) => {
             let mut found = false;
@@ -87,6 +93,9 @@ fn string_and_one_format_get_items(code: &mut Code, layout: &Layout) -> Result<V
             let mut found = false;
             let mut code_clone_clone = code_clone.clone();
             while let Ok(items) = string_and_format_get_items(&mut code_clone_clone, inner) {
+                if code_clone_clone.position() == code_clone.position() {
+                    break;
+                }
                 output.push(C::Repeat(items));
                 code_clone = code_clone_clone.clone();
                 found = true;
@@ -96,6 +105,9 @@ fn string_and_one_format_get_items(code: &mut Code, layout: &Layout) -> Result<V
             }
         }
         Layout::Implementation => output.extend(implementation::peel(&mut code_clone)?),
+        Layout::CaseImplementation => {
+            output.extend(implementation::peel_case_arm(&mut code_clone)?)
+        }
     }
 
     *code = code_clone;
@@ -111,15 +123,19 @@ pub enum Layout {
     Text(&'static str),
     Identifier,
     IdentifierSub,
+    IdentifierList,
     Address,
+    Arguments,
+    CaseLabels,
     DataType,
-    Value,
+    VarSection,
     Expression,
     OneOf(&'static [&'static [Layout]]),
     Option(&'static [Layout]),
     BeginMiddleEnd(&'static [Layout], &'static [Layout], &'static [Layout]),
     Repeat(&'static [Layout]),
     Implementation,
+    CaseImplementation,
 }
 
 fn peel<'a>(code: &mut Code, text: &'a str) -> Result<&'a str> {
@@ -127,7 +143,12 @@ fn peel<'a>(code: &mut Code, text: &'a str) -> Result<&'a str> {
     Ok(text)
 }
 
-fn peel_uppercase<'a>(code: &mut Code, text: &'a str) -> Result<&'a str> {
-    *code = code.strip_prefix_uppercase(text)?;
-    Ok(text)
+fn peel_uppercase(code: &mut Code, text: &str) -> Result<Spelling> {
+    let code_stripped = match text.chars().last() {
+        Some(c) if c.is_alphanumeric() || c == '_' => code.strip_prefix_keyword(text)?,
+        Some(_) | None => code.strip_prefix_uppercase(text)?,
+    };
+    let spelling = Spelling::between(code, &code_stripped);
+    *code = code_stripped;
+    Ok(spelling)
 }
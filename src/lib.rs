@@ -0,0 +1,39 @@
+use std::fs;
+use std::io::Result;
+use std::path::Path;
+
+mod code;
+pub mod components;
+mod dazzle;
+pub mod declaration;
+pub mod fmt;
+pub mod implementation;
+mod layout;
+pub mod statement;
+pub mod structured_text;
+pub mod visit;
+
+pub use code::Span;
+
+pub fn visit_dirs(dir: &Path, cb: &dyn Fn(&Path) -> Result<()>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            visit_dirs(&path, cb)?;
+        } else {
+            let extension = match path.extension() {
+                Some(os_str) => os_str,
+                None => continue,
+            };
+
+            match extension.to_str() {
+                Some("TcPOU") | Some("TcDUT") | Some("TcTLEO") | Some("TcGVL") => (),
+                Some(_) | None => continue,
+            }
+
+            cb(&path)?;
+        }
+    }
+    Ok(())
+}
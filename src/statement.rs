@@ -0,0 +1,379 @@
+use crate::code::Span;
+use crate::components::{BeginMiddleEnd, CaseLabel, Component as C, Ether, Expression, Identifier};
+
+/// A statement lowered from the components of an implementation. It is a copy: changing it does
+/// not change the components, and so does not change the formatted output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statement {
+    pub span: Span,
+    pub leading: Vec<Ether>,
+    pub trailing: Vec<Ether>,
+    pub kind: StatementKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatementKind {
+    Assign {
+        target: Expression,
+        op: AssignOperator,
+        value: Expression,
+        /// Further targets of a chained assignment such as `a := b := c`, each followed by its
+        /// operator.
+        chain: Vec<(Expression, AssignOperator)>,
+    },
+    If {
+        /// The `IF` branch followed by each `ELSIF` branch. An `ELSIF` missing its condition is
+        /// left out, rather than the whole statement.
+        branches: Vec<Branch>,
+        else_body: Option<Vec<Statement>>,
+    },
+    Case {
+        selector: Expression,
+        arms: Vec<CaseArm>,
+        else_body: Option<Vec<Statement>>,
+    },
+    For {
+        var: Identifier,
+        from: Expression,
+        to: Expression,
+        by: Option<Expression>,
+        body: Vec<Statement>,
+    },
+    While {
+        condition: Expression,
+        body: Vec<Statement>,
+    },
+    Repeat {
+        body: Vec<Statement>,
+        until: Expression,
+    },
+    Return,
+    Exit,
+    Continue,
+    Jump(Identifier),
+    Label(Identifier),
+    Call(Expression),
+    /// An empty statement `;`, or one missing a part it needs, as a tree built by hand may be.
+    Empty,
+    /// A statement whose tokens do not form any of the above, such as an expression followed by
+    /// something other than an assignment.
+    Unrecognised,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssignOperator {
+    Assign,
+    Reset,
+    Set,
+    Reference,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Branch {
+    pub condition: Expression,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaseArm {
+    pub labels: Vec<CaseLabel>,
+    pub body: Vec<Statement>,
+}
+
+pub(crate) fn lower_all(components: &[C]) -> Vec<Statement> {
+    components
+        .iter()
+        .filter_map(|component| match component {
+            C::Statement(span, items) => Some(Statement::lower(*span, items)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn lower_middle(middle: &[Vec<C>]) -> Vec<Statement> {
+    middle.iter().flat_map(|m| lower_all(m)).collect()
+}
+
+impl Statement {
+    fn lower(span: Span, items: &[C]) -> Self {
+        let first = items
+            .iter()
+            .position(|c| !matches!(c, C::Ether(_)))
+            .unwrap_or(items.len());
+        let last = items
+            .iter()
+            .rposition(|c| !matches!(c, C::Ether(_)))
+            .map_or(first, |i| i + 1);
+
+        Self {
+            span,
+            leading: ethers(&items[..first]),
+            trailing: ethers(&items[last..]),
+            kind: StatementKind::lower(&items[first..last]),
+        }
+    }
+}
+
+impl StatementKind {
+    fn lower(items: &[C]) -> Self {
+        let tokens = tokens(items);
+        match tokens.first() {
+            Some(C::Uppercase("RETURN", _)) => Self::Return,
+            Some(C::Uppercase("EXIT", _)) => Self::Exit,
+            Some(C::Uppercase("CONTINUE", _)) => Self::Continue,
+            Some(C::Uppercase("JMP", _)) => match tokens.get(1) {
+                Some(C::Identifier(label)) => Self::Jump(label.clone()),
+                Some(_) | None => Self::Empty,
+            },
+            Some(C::Identifier(label)) => Self::Label(label.clone()),
+            Some(C::BeginMiddleEnd(block)) => Self::lower_block(block).unwrap_or(Self::Empty),
+            Some(C::Expression(expression))
+                if matches!(tokens.get(1), Some(C::Text(";")) | None) =>
+            {
+                Self::Call(expression.clone())
+            }
+            _ if tokens.iter().any(|token| assign_operator(token).is_some()) => {
+                Self::lower_assign(&tokens).unwrap_or(Self::Empty)
+            }
+            Some(C::Text(";")) | None => Self::Empty,
+            Some(_) => Self::Unrecognised,
+        }
+    }
+
+    fn lower_assign(tokens: &[&C]) -> Option<Self> {
+        let mut targets = Vec::new();
+        let mut value = None;
+        let mut op = None;
+        for token in tokens {
+            match token {
+                C::Expression(expression) => value = Some(expression.clone()),
+                _ => match assign_operator(token) {
+                    Some(operator) => op = Some(operator),
+                    None => continue,
+                },
+            }
+            if let (Some(expression), Some(operator)) = (&value, op) {
+                targets.push((expression.clone(), operator));
+                value = None;
+                op = None;
+            }
+        }
+
+        let mut targets = targets.into_iter();
+        let (target, op) = targets.next()?;
+        Some(Self::Assign {
+            target,
+            op,
+            value: value?,
+            chain: targets.collect(),
+        })
+    }
+
+    fn lower_block(block: &BeginMiddleEnd) -> Option<Self> {
+        let begin = tokens(&block.begin);
+        let kind = match begin.first() {
+            Some(C::Uppercase("IF", _)) => {
+                let mut branches = vec![Branch {
+                    condition: first_expression(&begin)?,
+                    body: lower_middle(&block.middle),
+                }];
+                let mut else_body = None;
+                for component in tokens(&block.end) {
+                    match component {
+                        C::BeginMiddleEnd(inner) if inner.keyword() == Some("ELSIF") => {
+                            if let Some(condition) = first_expression(&tokens(&inner.begin)) {
+                                branches.push(Branch {
+                                    condition,
+                                    body: lower_middle(&inner.middle),
+                                });
+                            }
+                        }
+                        C::BeginMiddleEnd(inner) if inner.keyword() == Some("ELSE") => {
+                            else_body = Some(lower_middle(&inner.middle))
+                        }
+                        _ => (),
+                    }
+                }
+                Self::If {
+                    branches,
+                    else_body,
+                }
+            }
+            Some(C::Uppercase("CASE", _)) => {
+                let mut arms = Vec::new();
+                let mut else_body = None;
+                for component in block.middle.iter().flat_map(|m| tokens(m)) {
+                    if let C::BeginMiddleEnd(arm) = component {
+                        if arm.keyword() == Some("ELSE") {
+                            else_body = Some(lower_middle(&arm.middle));
+                        } else {
+                            let labels = match tokens(&arm.begin).first() {
+                                Some(C::CaseLabels(labels)) => labels.0.clone(),
+                                Some(_) | None => Vec::new(),
+                            };
+                            arms.push(CaseArm {
+                                labels,
+                                body: lower_middle(&arm.middle),
+                            });
+                        }
+                    }
+                }
+                Self::Case {
+                    selector: first_expression(&begin)?,
+                    arms,
+                    else_body,
+                }
+            }
+            Some(C::Uppercase("FOR", _)) => {
+                let mut var = None;
+                let mut from = None;
+                let mut to = None;
+                let mut by = None;
+                let mut previous: Option<&C> = None;
+                for &token in &begin {
+                    match (previous, token) {
+                        (_, C::Identifier(identifier)) if var.is_none() => {
+                            var = Some(identifier.clone())
+                        }
+                        (Some(C::Text(":=")), C::Expression(expression)) => {
+                            from = Some(expression.clone())
+                        }
+                        (Some(C::Uppercase("TO", _)), C::Expression(expression)) => {
+                            to = Some(expression.clone())
+                        }
+                        (Some(C::Uppercase("BY", _)), C::Expression(expression)) => {
+                            by = Some(expression.clone())
+                        }
+                        _ => (),
+                    }
+                    previous = Some(token);
+                }
+                Self::For {
+                    var: var?,
+                    from: from?,
+                    to: to?,
+                    by,
+                    body: lower_middle(&block.middle),
+                }
+            }
+            Some(C::Uppercase("WHILE", _)) => Self::While {
+                condition: first_expression(&begin)?,
+                body: lower_middle(&block.middle),
+            },
+            Some(C::Uppercase("REPEAT", _)) => Self::Repeat {
+                body: lower_middle(&block.middle),
+                until: first_expression(&tokens(&block.end))?,
+            },
+            Some(_) => Self::Unrecognised,
+            None => Self::Empty,
+        };
+        Some(kind)
+    }
+}
+
+fn assign_operator(token: &C) -> Option<AssignOperator> {
+    match token {
+        C::Text(":=") => Some(AssignOperator::Assign),
+        C::Uppercase("R=", _) => Some(AssignOperator::Reset),
+        C::Uppercase("S=", _) => Some(AssignOperator::Set),
+        C::Uppercase("REF=", _) => Some(AssignOperator::Reference),
+        _ => None,
+    }
+}
+
+fn tokens(items: &[C]) -> Vec<&C> {
+    let mut output = Vec::new();
+    for item in items {
+        match item {
+            C::Ether(_) | C::Space | C::Filler(_) => (),
+            C::Repeat(inner) => output.extend(tokens(inner)),
+            _ => output.push(item),
+        }
+    }
+    output
+}
+
+fn expressions(tokens: &[&C]) -> Vec<Expression> {
+    tokens
+        .iter()
+        .filter_map(|token| match token {
+            C::Expression(expression) => Some(expression.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn first_expression(tokens: &[&C]) -> Option<Expression> {
+    expressions(tokens).into_iter().next()
+}
+
+fn ethers(items: &[C]) -> Vec<Ether> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            C::Ether(ether) => Some(ether.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the length of `text` without any trailing whitespace, comments or pragmas.
+pub(crate) fn trim_trivia_end(text: &str) -> usize {
+    let mut end = 0;
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let rest = &text[i..];
+        if c == '\'' || c == '"' {
+            let mut escape = false;
+            let mut length = text.len() - i;
+            for (j, d) in rest.char_indices().skip(1) {
+                if escape {
+                    escape = false;
+                } else if d == '$' {
+                    escape = true;
+                } else if d == c {
+                    length = j + d.len_utf8();
+                    break;
+                }
+            }
+            i += length;
+            end = i;
+        } else if rest.starts_with("//") {
+            i += rest.find('\n').unwrap_or(rest.len());
+        } else if rest.starts_with("(*") {
+            i += skip_nested(rest, "(*", "*)");
+        } else if c == '{' {
+            i += skip_nested(rest, "{", "}");
+        } else {
+            i += c.len_utf8();
+            if !c.is_whitespace() {
+                end = i;
+            }
+        }
+    }
+    end
+}
+
+fn skip_nested(text: &str, start: &str, end: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < text.len() {
+        if text[i..].starts_with(start) {
+            depth += 1;
+            i += start.len();
+        } else if text[i..].starts_with(end) {
+            depth -= 1;
+            i += end.len();
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += text[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+#[path = "./test_statement.rs"]
+mod test_statement;
@@ -233,7 +233,7 @@ impl File {
         Ok(())
     }
 
-    pub fn for_each_declaration(&mut self, cb: fn(&str) -> Result<String>) -> Result<()> {
+    pub fn for_each_declaration(&mut self, cb: impl Fn(&str) -> Result<String>) -> Result<()> {
         let declaration = cb(&self.declaration)?;
         self.declaration = declaration;
         for chunk in self.chunks.iter_mut() {
@@ -248,7 +248,27 @@ impl File {
         Ok(())
     }
 
-    pub fn for_each_implementation(&mut self, cb: fn(&str) -> Result<String>) -> Result<()> {
+    /// Like [`Self::for_each_implementation`], but also gives `cb` the declarations the
+    /// implementation can see: the file's first, then any since the previous implementation, such
+    /// as that of a method or property.
+    pub fn for_each_implementation_with_declarations(
+        &mut self,
+        cb: impl Fn(&str, &[&str]) -> Result<String>,
+    ) -> Result<()> {
+        let mut declarations = vec![self.declaration.as_str()];
+        for chunk in self.chunks.iter_mut() {
+            match chunk.what {
+                Content::Declaration => declarations.push(&chunk.content),
+                Content::Implementation => {
+                    chunk.content = cb(&chunk.content, &declarations)?;
+                    declarations.truncate(1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn for_each_implementation(&mut self, cb: impl Fn(&str) -> Result<String>) -> Result<()> {
         for chunk in self.chunks.iter_mut() {
             match chunk.what {
                 Content::Declaration => continue,
@@ -0,0 +1,276 @@
+use std::str::FromStr;
+
+use super::*;
+use crate::components::ExpressionInner;
+use crate::implementation::Implementation;
+use crate::visit::{walk_begin_middle_end_mut, VisitMut};
+
+fn statements(input: &str) -> Vec<Statement> {
+    Implementation::from_str(input).unwrap().statements()
+}
+
+#[test]
+fn assignment() {
+    let input = "// leading\nx := y + 1; // trailing\n";
+    let statements = statements(input);
+
+    assert_eq!(statements.len(), 1);
+    let statement = &statements[0];
+    assert_eq!(
+        &input[statement.span.start..statement.span.end],
+        "x := y + 1;"
+    );
+    assert_eq!(statement.leading.len(), 2);
+    assert!(statement.leading[0].is_comment());
+    assert!(statement.trailing[0].is_comment());
+    assert!(matches!(
+        statement.kind,
+        StatementKind::Assign {
+            op: AssignOperator::Assign,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn assignment_chain() {
+    let statements = statements("a R= b := c;");
+
+    match &statements[0].kind {
+        StatementKind::Assign { op, chain, .. } => {
+            assert_eq!(*op, AssignOperator::Reset);
+            assert_eq!(chain.len(), 1);
+            assert_eq!(chain[0].1, AssignOperator::Assign);
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
+
+#[test]
+fn if_elsif_else() {
+    let input = "IF a THEN
+    x := 1;
+    y := 2;
+ELSIF b THEN
+    RETURN;
+ELSE
+    EXIT;
+END_IF
+";
+    let statements = statements(input);
+
+    assert_eq!(statements.len(), 1);
+    assert_eq!(
+        &input[statements[0].span.start..statements[0].span.end],
+        input.trim_end()
+    );
+    match &statements[0].kind {
+        StatementKind::If {
+            branches,
+            else_body,
+        } => {
+            assert_eq!(branches.len(), 2);
+            assert_eq!(branches[0].body.len(), 2);
+            assert_eq!(branches[1].body[0].kind, StatementKind::Return);
+            assert_eq!(else_body.as_ref().unwrap()[0].kind, StatementKind::Exit);
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
+
+#[test]
+fn case() {
+    let statements = statements(
+        "CASE state OF
+    1 :
+        do_thing();
+    2 :
+        ;
+    ELSE
+        state := 1;
+END_CASE",
+    );
+
+    match &statements[0].kind {
+        StatementKind::Case {
+            arms, else_body, ..
+        } => {
+            assert_eq!(arms.len(), 2);
+            assert!(matches!(arms[0].body[0].kind, StatementKind::Call(_)));
+            assert_eq!(arms[1].body[0].kind, StatementKind::Empty);
+            assert_eq!(else_body.as_ref().unwrap().len(), 1);
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
+
+#[test]
+fn loops() {
+    let statements = statements(
+        "FOR i := 0 TO 10 BY 2 DO
+    WHILE x DO
+        x := FALSE;
+    END_WHILE
+END_FOR
+REPEAT
+    n := n + 1;
+UNTIL n > 3
+END_REPEAT",
+    );
+
+    assert_eq!(statements.len(), 2);
+    match &statements[0].kind {
+        StatementKind::For { var, by, body, .. } => {
+            assert_eq!(var.0, "i");
+            assert!(by.is_some());
+            assert!(matches!(body[0].kind, StatementKind::While { .. }));
+        }
+        kind => panic!("{kind:?}"),
+    }
+    assert!(matches!(statements[1].kind, StatementKind::Repeat { .. }));
+}
+
+#[test]
+fn trivia_end() {
+    assert_eq!(trim_trivia_end("x := '//'; // comment\n"), 10);
+    assert_eq!(trim_trivia_end("x; (* a (* b *) *) {pragma}\n\n"), 2);
+}
+
+#[test]
+fn jumps() {
+    let statements = statements("JMP done;\nCONTINUE;\ndone:\n");
+
+    assert_eq!(
+        statements
+            .iter()
+            .map(|statement| statement.kind.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            StatementKind::Jump(Identifier(String::from("done"))),
+            StatementKind::Continue,
+            StatementKind::Label(Identifier(String::from("done"))),
+        ]
+    );
+}
+
+#[test]
+fn case_label_ranges() {
+    let statements = statements("CASE x OF\n    1, 3..5, -2 : y := 1;\nEND_CASE");
+
+    match &statements[0].kind {
+        StatementKind::Case { arms, .. } => {
+            assert_eq!(arms[0].labels.len(), 3);
+            assert!(matches!(arms[0].labels[1], CaseLabel::Range(_, _)));
+            assert_eq!(arms[0].body.len(), 1);
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
+
+#[test]
+fn for_step_expression() {
+    let statements = statements("FOR i := n TO 0 BY -(nStep * 2) DO\n    x := i;\nEND_FOR");
+
+    match &statements[0].kind {
+        StatementKind::For { var, from, by, .. } => {
+            assert_eq!(var.0, "i");
+            assert!(matches!(from.0, ExpressionInner::Value(_)));
+            assert!(matches!(
+                by.as_ref().unwrap().0,
+                ExpressionInner::Negative(_)
+            ));
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
+
+/// Removes every expression and identifier, as a caller editing the tree might.
+fn strip(components: &mut Vec<C>) {
+    components.retain(|c| !matches!(c, C::Expression(_) | C::Identifier(_)));
+    for component in components {
+        match component {
+            C::Statement(_, items) | C::Repeat(items) => strip(items),
+            C::BeginMiddleEnd(block) => {
+                strip(&mut block.begin);
+                block.middle.iter_mut().for_each(strip);
+                strip(&mut block.end);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[test]
+fn missing_parts() {
+    for input in [
+        "x := 1;",
+        "IF a THEN\n    x := 1;\nEND_IF",
+        "CASE n OF\n    1: x := 1;\nEND_CASE",
+        "FOR i := 0 TO 9 DO\n    x := i;\nEND_FOR",
+        "WHILE a DO\n    x := 1;\nEND_WHILE",
+        "REPEAT\n    x := 1;\nUNTIL a\nEND_REPEAT",
+    ] {
+        let mut implementation = Implementation::from_str(input).unwrap();
+        strip(&mut implementation.0);
+        let statements = implementation.statements();
+        assert_eq!(statements.len(), 1, "{input}");
+        assert_eq!(statements[0].kind, StatementKind::Empty, "{input}");
+    }
+}
+
+struct StripFirstElsif(bool);
+
+impl VisitMut for StripFirstElsif {
+    fn visit_begin_middle_end_mut(&mut self, block: &mut BeginMiddleEnd) {
+        if block.keyword() == Some("ELSIF") && !self.0 {
+            strip(&mut block.begin);
+            self.0 = true;
+        }
+        walk_begin_middle_end_mut(self, block);
+    }
+}
+
+#[test]
+fn missing_elsif_condition() {
+    let input =
+        "IF a THEN\n    x := 1;\nELSIF b THEN\n    x := 2;\nELSIF c THEN\n    x := 3;\nEND_IF";
+    let mut implementation = Implementation::from_str(input).unwrap();
+    StripFirstElsif(false).visit_implementation_mut(&mut implementation);
+
+    match &implementation.statements()[0].kind {
+        StatementKind::If { branches, .. } => {
+            assert_eq!(branches.len(), 2);
+            let span = branches[1].body[0].span;
+            assert_eq!(&input[span.start..span.end], "x := 3;");
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
+
+#[test]
+fn unrecognised() {
+    let mut implementation = Implementation::from_str("x := 1;").unwrap();
+    if let C::Statement(_, items) = &mut implementation.0[0] {
+        items.retain(|c| !matches!(c, C::Text(":=")));
+    }
+
+    let statements = implementation.statements();
+    assert_eq!(statements[0].kind, StatementKind::Unrecognised);
+}
+
+#[test]
+fn case_jump_label() {
+    let statements = statements("CASE x OF 1: lbl: y := 1; JMP lbl; END_CASE");
+
+    match &statements[0].kind {
+        StatementKind::Case { arms, .. } => {
+            assert_eq!(arms.len(), 1);
+            assert_eq!(
+                arms[0].body[0].kind,
+                StatementKind::Label(Identifier(String::from("lbl")))
+            );
+            assert_eq!(arms[0].body.len(), 3);
+        }
+        kind => panic!("{kind:?}"),
+    }
+}
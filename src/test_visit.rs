@@ -0,0 +1,136 @@
+use std::str::FromStr;
+
+use super::*;
+use crate::components::{Conditions, Defines};
+use crate::dazzle::{Dazzle, Dazzler};
+
+#[derive(Default)]
+struct Counter {
+    identifiers: Vec<String>,
+    operators: usize,
+    data_types: usize,
+}
+
+impl Visit for Counter {
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        self.identifiers.push(identifier.0.clone());
+    }
+
+    fn visit_operator(&mut self, _operator: &Operator) {
+        self.operators += 1;
+    }
+
+    fn visit_data_type(&mut self, data_type: &DataType) {
+        self.data_types += 1;
+        walk_data_type(self, data_type);
+    }
+}
+
+#[test]
+fn declaration() {
+    let declaration = Declaration::from_str(
+        "FUNCTION_BLOCK Thing
+VAR
+    a, b : ARRAY [0..3] OF INT := [1 + 2, 3, 4, 5];
+    c : POINTER TO REAL;
+END_VAR
+",
+    )
+    .unwrap();
+
+    let mut counter = Counter::default();
+    counter.visit_declaration(&declaration);
+
+    assert_eq!(counter.identifiers, vec!["Thing", "a", "b", "c"]);
+    assert_eq!(counter.operators, 1);
+    assert_eq!(counter.data_types, 4);
+}
+
+#[test]
+fn implementation_and_statements() {
+    let implementation = Implementation::from_str(
+        "IF x > 0 THEN
+    y := f(in := x * 2);
+END_IF
+",
+    )
+    .unwrap();
+
+    let mut counter = Counter::default();
+    counter.visit_implementation(&implementation);
+    assert_eq!(counter.identifiers, vec!["in"]);
+    assert_eq!(counter.operators, 2);
+
+    let mut counter = Counter::default();
+    for statement in implementation.statements() {
+        counter.visit_statement(&statement);
+    }
+    assert_eq!(counter.identifiers, vec!["in"]);
+    assert_eq!(counter.operators, 2);
+}
+
+struct Negate;
+
+impl VisitMut for Negate {
+    fn visit_operator_mut(&mut self, operator: &mut Operator) {
+        if *operator == Operator::GreaterThan {
+            *operator = Operator::LessThanOrEqual;
+        }
+    }
+}
+
+#[test]
+fn rewrite() {
+    let mut implementation = Implementation::from_str("a := b > c;\n").unwrap();
+    Negate.visit_implementation_mut(&mut implementation);
+
+    let mut dazzler = Dazzler::default();
+    for component in &implementation.0 {
+        component.dazzle(&mut dazzler);
+    }
+    assert_eq!(dazzler.f, "a := b <= c;\n");
+}
+
+#[test]
+fn conditional_branches() {
+    struct Active<'a> {
+        conditions: Conditions<'a>,
+        identifiers: Vec<String>,
+    }
+
+    impl Visit for Active<'_> {
+        fn visit_ether(&mut self, ether: &Ether) {
+            if let Some(conditional) = ether.conditional() {
+                self.conditions.update(conditional);
+            }
+        }
+
+        fn visit_identifier(&mut self, identifier: &Identifier) {
+            if self.conditions.is_active() {
+                self.identifiers.push(identifier.0.clone());
+            }
+        }
+    }
+
+    let declaration = Declaration::from_str(
+        "FUNCTION_BLOCK Thing
+VAR
+    {IF defined (SIMULATION)}
+    simulated : INT;
+    {ELSE}
+    real : INT;
+    {END_IF}
+    always : INT;
+END_VAR
+",
+    )
+    .unwrap();
+
+    let defines = Defines::from([(String::from("SIMULATION"), String::new())]);
+    let mut active = Active {
+        conditions: Conditions::new(&defines),
+        identifiers: Vec::new(),
+    };
+    active.visit_declaration(&declaration);
+    assert_eq!(active.identifiers, vec!["Thing", "simulated", "always"]);
+}
@@ -0,0 +1,872 @@
+use crate::components::{
+    Address, Argument, ArgumentList, Array, ArrayElement, ArrayRange, BeginMiddleEnd,
+    BinaryOperator, CaseLabel, Component as C, DataType, Ether, Expression, ExpressionInner,
+    FunctionCall, Identifier, IdentifierSub, Literal, Member, Operator, StringLiteral, Struct,
+    Value, ValueInner, VarSection,
+};
+use crate::declaration::Declaration;
+use crate::implementation::Implementation;
+use crate::statement::{Statement, StatementKind};
+
+/// Walks the syntax tree by reference.
+///
+/// Every method defaults to visiting the node's children, so implementations only need to
+/// override the nodes they are interested in, calling the matching `walk_*` function to keep
+/// descending.
+pub trait Visit {
+    fn visit_declaration(&mut self, declaration: &Declaration) {
+        walk_declaration(self, declaration);
+    }
+
+    fn visit_implementation(&mut self, implementation: &Implementation) {
+        walk_implementation(self, implementation);
+    }
+
+    fn visit_component(&mut self, component: &C) {
+        walk_component(self, component);
+    }
+
+    fn visit_begin_middle_end(&mut self, begin_middle_end: &BeginMiddleEnd) {
+        walk_begin_middle_end(self, begin_middle_end);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_case_label(&mut self, case_label: &CaseLabel) {
+        walk_case_label(self, case_label);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_expression_inner(&mut self, expression: &ExpressionInner) {
+        walk_expression_inner(self, expression);
+    }
+
+    fn visit_binary_operator(&mut self, binary_operator: &BinaryOperator) {
+        walk_binary_operator(self, binary_operator);
+    }
+
+    fn visit_function_call(&mut self, function_call: &FunctionCall) {
+        walk_function_call(self, function_call);
+    }
+
+    fn visit_argument(&mut self, argument: &Argument) {
+        walk_argument(self, argument);
+    }
+
+    fn visit_argument_list(&mut self, argument_list: &ArgumentList) {
+        walk_argument_list(self, argument_list);
+    }
+
+    fn visit_value(&mut self, value: &Value) {
+        walk_value(self, value);
+    }
+
+    fn visit_array(&mut self, array: &Array) {
+        walk_array(self, array);
+    }
+
+    fn visit_struct(&mut self, s: &Struct) {
+        walk_struct(self, s);
+    }
+
+    fn visit_data_type(&mut self, data_type: &DataType) {
+        walk_data_type(self, data_type);
+    }
+
+    fn visit_array_range(&mut self, array_range: &ArrayRange) {
+        walk_array_range(self, array_range);
+    }
+
+    fn visit_ether(&mut self, _ether: &Ether) {}
+
+    fn visit_address(&mut self, _address: &Address) {}
+
+    fn visit_literal(&mut self, _literal: &Literal) {}
+
+    fn visit_string_literal(&mut self, _literal: &StringLiteral) {}
+
+    fn visit_var_section(&mut self, _var_section: &VarSection) {}
+
+    fn visit_identifier(&mut self, _identifier: &Identifier) {}
+
+    fn visit_identifier_sub(&mut self, _identifier_sub: &IdentifierSub) {}
+
+    fn visit_member(&mut self, _member: &Member) {}
+
+    fn visit_operator(&mut self, _operator: &Operator) {}
+}
+
+pub fn walk_declaration<V: Visit + ?Sized>(visitor: &mut V, declaration: &Declaration) {
+    for component in &declaration.0 {
+        visitor.visit_component(component);
+    }
+}
+
+pub fn walk_implementation<V: Visit + ?Sized>(visitor: &mut V, implementation: &Implementation) {
+    for component in &implementation.0 {
+        visitor.visit_component(component);
+    }
+}
+
+pub fn walk_component<V: Visit + ?Sized>(visitor: &mut V, component: &C) {
+    match component {
+        C::Ether(inner) => visitor.visit_ether(inner),
+        C::Address(inner) => visitor.visit_address(inner),
+        C::Arguments(inner) => visitor.visit_argument_list(inner),
+        C::CaseLabels(inner) => {
+            for label in &inner.0 {
+                visitor.visit_case_label(label);
+            }
+        }
+        C::DataType(inner) => visitor.visit_data_type(inner),
+        C::Expression(inner) => visitor.visit_expression(inner),
+        C::Identifier(inner) => visitor.visit_identifier(inner),
+        C::IdentifierSub(inner) => visitor.visit_identifier_sub(inner),
+        C::IdentifierList(inner) => {
+            for identifier in &inner.0 {
+                visitor.visit_identifier_sub(identifier);
+            }
+        }
+        C::Value(inner) => visitor.visit_value(inner),
+        C::VarSection(inner) => visitor.visit_var_section(inner),
+        C::BeginMiddleEnd(inner) => visitor.visit_begin_middle_end(inner),
+        C::Repeat(inners) | C::Statement(_, inners) => {
+            for inner in inners {
+                visitor.visit_component(inner);
+            }
+        }
+        C::Space | C::Text(_) | C::Uppercase(..) | C::Filler(_) => (),
+    }
+}
+
+pub fn walk_begin_middle_end<V: Visit + ?Sized>(
+    visitor: &mut V,
+    begin_middle_end: &BeginMiddleEnd,
+) {
+    for component in &begin_middle_end.begin {
+        visitor.visit_component(component);
+    }
+    for components in &begin_middle_end.middle {
+        for component in components {
+            visitor.visit_component(component);
+        }
+    }
+    for component in &begin_middle_end.end {
+        visitor.visit_component(component);
+    }
+}
+
+pub fn walk_statement<V: Visit + ?Sized>(visitor: &mut V, statement: &Statement) {
+    for ether in &statement.leading {
+        visitor.visit_ether(ether);
+    }
+    match &statement.kind {
+        StatementKind::Assign {
+            target,
+            value,
+            chain,
+            ..
+        } => {
+            visitor.visit_expression(target);
+            for (expression, _) in chain {
+                visitor.visit_expression(expression);
+            }
+            visitor.visit_expression(value);
+        }
+        StatementKind::If {
+            branches,
+            else_body,
+        } => {
+            for branch in branches {
+                visitor.visit_expression(&branch.condition);
+                walk_statements(visitor, &branch.body);
+            }
+            if let Some(body) = else_body {
+                walk_statements(visitor, body);
+            }
+        }
+        StatementKind::Case {
+            selector,
+            arms,
+            else_body,
+        } => {
+            visitor.visit_expression(selector);
+            for arm in arms {
+                for label in &arm.labels {
+                    visitor.visit_case_label(label);
+                }
+                walk_statements(visitor, &arm.body);
+            }
+            if let Some(body) = else_body {
+                walk_statements(visitor, body);
+            }
+        }
+        StatementKind::For {
+            var,
+            from,
+            to,
+            by,
+            body,
+        } => {
+            visitor.visit_identifier(var);
+            visitor.visit_expression(from);
+            visitor.visit_expression(to);
+            if let Some(by) = by {
+                visitor.visit_expression(by);
+            }
+            walk_statements(visitor, body);
+        }
+        StatementKind::While { condition, body } => {
+            visitor.visit_expression(condition);
+            walk_statements(visitor, body);
+        }
+        StatementKind::Repeat { body, until } => {
+            walk_statements(visitor, body);
+            visitor.visit_expression(until);
+        }
+        StatementKind::Jump(label) | StatementKind::Label(label) => visitor.visit_identifier(label),
+        StatementKind::Call(expression) => visitor.visit_expression(expression),
+        StatementKind::Return
+        | StatementKind::Exit
+        | StatementKind::Continue
+        | StatementKind::Empty
+        | StatementKind::Unrecognised => (),
+    }
+    for ether in &statement.trailing {
+        visitor.visit_ether(ether);
+    }
+}
+
+fn walk_statements<V: Visit + ?Sized>(visitor: &mut V, statements: &[Statement]) {
+    for statement in statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_case_label<V: Visit + ?Sized>(visitor: &mut V, case_label: &CaseLabel) {
+    match case_label {
+        CaseLabel::Single(value) => visitor.visit_expression(value),
+        CaseLabel::Range(lower, upper) => {
+            visitor.visit_expression(lower);
+            visitor.visit_expression(upper);
+        }
+    }
+}
+
+pub fn walk_expression<V: Visit + ?Sized>(visitor: &mut V, expression: &Expression) {
+    visitor.visit_expression_inner(&expression.0);
+}
+
+pub fn walk_expression_inner<V: Visit + ?Sized>(visitor: &mut V, expression: &ExpressionInner) {
+    match expression {
+        ExpressionInner::BinaryOperator(inner) => visitor.visit_binary_operator(inner),
+        ExpressionInner::Bracket(ethers0, inner, ethers1) => {
+            for ether in ethers0 {
+                visitor.visit_ether(ether);
+            }
+            visitor.visit_expression_inner(inner);
+            for ether in ethers1 {
+                visitor.visit_ether(ether);
+            }
+        }
+        ExpressionInner::Dereference(inner)
+        | ExpressionInner::Negative(inner)
+        | ExpressionInner::Positive(inner) => visitor.visit_expression_inner(inner),
+        ExpressionInner::Field(inner, member) => {
+            visitor.visit_expression_inner(inner);
+            visitor.visit_member(member);
+        }
+        ExpressionInner::FunctionCall(inner) => visitor.visit_function_call(inner),
+        ExpressionInner::Index(inner, indices) => {
+            visitor.visit_expression_inner(inner);
+            for index in indices {
+                visitor.visit_expression_inner(index);
+            }
+        }
+        ExpressionInner::Method(inner, method) => {
+            visitor.visit_expression_inner(inner);
+            visitor.visit_function_call(method);
+        }
+        ExpressionInner::Not(_, ethers, inner) => {
+            for ether in ethers {
+                visitor.visit_ether(ether);
+            }
+            visitor.visit_expression_inner(inner);
+        }
+        ExpressionInner::Value(inner) => visitor.visit_value(inner),
+    }
+}
+
+pub fn walk_binary_operator<V: Visit + ?Sized>(visitor: &mut V, binary_operator: &BinaryOperator) {
+    visitor.visit_expression_inner(&binary_operator.left);
+    for ether in &binary_operator.ethers0 {
+        visitor.visit_ether(ether);
+    }
+    visitor.visit_operator(&binary_operator.operator);
+    for ether in &binary_operator.ethers1 {
+        visitor.visit_ether(ether);
+    }
+    visitor.visit_expression_inner(&binary_operator.right);
+}
+
+pub fn walk_function_call<V: Visit + ?Sized>(visitor: &mut V, function_call: &FunctionCall) {
+    visitor.visit_expression_inner(&function_call.identifier);
+    for ether in &function_call.ethers {
+        visitor.visit_ether(ether);
+    }
+    for (argument, ethers) in &function_call.arguments {
+        visitor.visit_argument(argument);
+        for ether in ethers {
+            visitor.visit_ether(ether);
+        }
+    }
+}
+
+pub fn walk_argument_list<V: Visit + ?Sized>(visitor: &mut V, argument_list: &ArgumentList) {
+    for ether in &argument_list.ethers {
+        visitor.visit_ether(ether);
+    }
+    for (argument, ethers) in &argument_list.arguments {
+        visitor.visit_argument(argument);
+        for ether in ethers {
+            visitor.visit_ether(ether);
+        }
+    }
+}
+
+pub fn walk_argument<V: Visit + ?Sized>(visitor: &mut V, argument: &Argument) {
+    let expression = match argument {
+        Argument::Unnamed(expression) => expression,
+        Argument::InputOrInout(identifier, expression)
+        | Argument::Output(identifier, expression) => {
+            visitor.visit_identifier(identifier);
+            expression
+        }
+    };
+    if let Some(expression) = expression {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_value<V: Visit + ?Sized>(visitor: &mut V, value: &Value) {
+    match &value.0 {
+        ValueInner::Array(inner) => visitor.visit_array(inner),
+        ValueInner::Struct(inner) => visitor.visit_struct(inner),
+        ValueInner::Literal(literal) => visitor.visit_literal(literal),
+        ValueInner::String(literal) => visitor.visit_string_literal(literal),
+        ValueInner::Flat(_) => (),
+    }
+}
+
+pub fn walk_array<V: Visit + ?Sized>(visitor: &mut V, array: &Array) {
+    for ether in &array.0 {
+        visitor.visit_ether(ether);
+    }
+    for (element, ethers) in &array.1 {
+        match element {
+            ArrayElement::Single(expression) => visitor.visit_expression(expression),
+            ArrayElement::Repeated(count, value) => {
+                visitor.visit_literal(count);
+                if let Some(value) = value {
+                    visitor.visit_expression(value);
+                }
+            }
+        }
+        for ether in ethers {
+            visitor.visit_ether(ether);
+        }
+    }
+}
+
+pub fn walk_struct<V: Visit + ?Sized>(visitor: &mut V, s: &Struct) {
+    for (identifier, expression, ethers) in &s.0 {
+        visitor.visit_identifier(identifier);
+        visitor.visit_expression(expression);
+        for ether in ethers {
+            visitor.visit_ether(ether);
+        }
+    }
+}
+
+pub fn walk_data_type<V: Visit + ?Sized>(visitor: &mut V, data_type: &DataType) {
+    match data_type {
+        DataType::Array(ranges, inner, _) => {
+            for range in ranges {
+                visitor.visit_array_range(range);
+            }
+            visitor.visit_data_type(inner);
+        }
+        DataType::ReferenceTo(inner, _) | DataType::PointerTo(inner, _) => {
+            visitor.visit_data_type(inner)
+        }
+        DataType::Subrange(inner, lower, upper) => {
+            visitor.visit_data_type(inner);
+            visitor.visit_expression(lower);
+            visitor.visit_expression(upper);
+        }
+        DataType::ImplicitEnum(members) => {
+            for member in members {
+                visitor.visit_identifier(member);
+            }
+        }
+        DataType::String(length, _) | DataType::WString(length, _) => {
+            if let Some(length) = length {
+                visitor.visit_expression(length);
+            }
+        }
+        DataType::Elementary(..) | DataType::Flat(_) => (),
+    }
+}
+
+pub fn walk_array_range<V: Visit + ?Sized>(visitor: &mut V, array_range: &ArrayRange) {
+    if let ArrayRange::LowerUpper(lower, upper) = array_range {
+        visitor.visit_expression(lower);
+        visitor.visit_expression(upper);
+    }
+}
+
+/// Walks the syntax tree by mutable reference, allowing nodes to be rewritten in place.
+///
+/// As with [`Visit`], every method defaults to visiting the node's children.
+pub trait VisitMut {
+    fn visit_declaration_mut(&mut self, declaration: &mut Declaration) {
+        walk_declaration_mut(self, declaration);
+    }
+
+    fn visit_implementation_mut(&mut self, implementation: &mut Implementation) {
+        walk_implementation_mut(self, implementation);
+    }
+
+    fn visit_component_mut(&mut self, component: &mut C) {
+        walk_component_mut(self, component);
+    }
+
+    fn visit_begin_middle_end_mut(&mut self, begin_middle_end: &mut BeginMiddleEnd) {
+        walk_begin_middle_end_mut(self, begin_middle_end);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_case_label_mut(&mut self, case_label: &mut CaseLabel) {
+        walk_case_label_mut(self, case_label);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    fn visit_expression_inner_mut(&mut self, expression: &mut ExpressionInner) {
+        walk_expression_inner_mut(self, expression);
+    }
+
+    fn visit_binary_operator_mut(&mut self, binary_operator: &mut BinaryOperator) {
+        walk_binary_operator_mut(self, binary_operator);
+    }
+
+    fn visit_function_call_mut(&mut self, function_call: &mut FunctionCall) {
+        walk_function_call_mut(self, function_call);
+    }
+
+    fn visit_argument_mut(&mut self, argument: &mut Argument) {
+        walk_argument_mut(self, argument);
+    }
+
+    fn visit_argument_list_mut(&mut self, argument_list: &mut ArgumentList) {
+        walk_argument_list_mut(self, argument_list);
+    }
+
+    fn visit_value_mut(&mut self, value: &mut Value) {
+        walk_value_mut(self, value);
+    }
+
+    fn visit_array_mut(&mut self, array: &mut Array) {
+        walk_array_mut(self, array);
+    }
+
+    fn visit_struct_mut(&mut self, s: &mut Struct) {
+        walk_struct_mut(self, s);
+    }
+
+    fn visit_data_type_mut(&mut self, data_type: &mut DataType) {
+        walk_data_type_mut(self, data_type);
+    }
+
+    fn visit_array_range_mut(&mut self, array_range: &mut ArrayRange) {
+        walk_array_range_mut(self, array_range);
+    }
+
+    fn visit_ether_mut(&mut self, _ether: &mut Ether) {}
+
+    fn visit_address_mut(&mut self, _address: &mut Address) {}
+
+    fn visit_literal_mut(&mut self, _literal: &mut Literal) {}
+
+    fn visit_string_literal_mut(&mut self, _literal: &mut StringLiteral) {}
+
+    fn visit_var_section_mut(&mut self, _var_section: &mut VarSection) {}
+
+    fn visit_identifier_mut(&mut self, _identifier: &mut Identifier) {}
+
+    fn visit_identifier_sub_mut(&mut self, _identifier_sub: &mut IdentifierSub) {}
+
+    fn visit_member_mut(&mut self, _member: &mut Member) {}
+
+    fn visit_operator_mut(&mut self, _operator: &mut Operator) {}
+}
+
+pub fn walk_declaration_mut<V: VisitMut + ?Sized>(visitor: &mut V, declaration: &mut Declaration) {
+    for component in &mut declaration.0 {
+        visitor.visit_component_mut(component);
+    }
+}
+
+pub fn walk_implementation_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    implementation: &mut Implementation,
+) {
+    for component in &mut implementation.0 {
+        visitor.visit_component_mut(component);
+    }
+}
+
+pub fn walk_component_mut<V: VisitMut + ?Sized>(visitor: &mut V, component: &mut C) {
+    match component {
+        C::Ether(inner) => visitor.visit_ether_mut(inner),
+        C::Address(inner) => visitor.visit_address_mut(inner),
+        C::Arguments(inner) => visitor.visit_argument_list_mut(inner),
+        C::CaseLabels(inner) => {
+            for label in &mut inner.0 {
+                visitor.visit_case_label_mut(label);
+            }
+        }
+        C::DataType(inner) => visitor.visit_data_type_mut(inner),
+        C::Expression(inner) => visitor.visit_expression_mut(inner),
+        C::Identifier(inner) => visitor.visit_identifier_mut(inner),
+        C::IdentifierSub(inner) => visitor.visit_identifier_sub_mut(inner),
+        C::IdentifierList(inner) => {
+            for identifier in &mut inner.0 {
+                visitor.visit_identifier_sub_mut(identifier);
+            }
+        }
+        C::Value(inner) => visitor.visit_value_mut(inner),
+        C::VarSection(inner) => visitor.visit_var_section_mut(inner),
+        C::BeginMiddleEnd(inner) => visitor.visit_begin_middle_end_mut(inner),
+        C::Repeat(inners) | C::Statement(_, inners) => {
+            for inner in inners {
+                visitor.visit_component_mut(inner);
+            }
+        }
+        C::Space | C::Text(_) | C::Uppercase(..) | C::Filler(_) => (),
+    }
+}
+
+pub fn walk_begin_middle_end_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    begin_middle_end: &mut BeginMiddleEnd,
+) {
+    for component in &mut begin_middle_end.begin {
+        visitor.visit_component_mut(component);
+    }
+    for components in &mut begin_middle_end.middle {
+        for component in components {
+            visitor.visit_component_mut(component);
+        }
+    }
+    for component in &mut begin_middle_end.end {
+        visitor.visit_component_mut(component);
+    }
+}
+
+pub fn walk_statement_mut<V: VisitMut + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    for ether in &mut statement.leading {
+        visitor.visit_ether_mut(ether);
+    }
+    match &mut statement.kind {
+        StatementKind::Assign {
+            target,
+            value,
+            chain,
+            ..
+        } => {
+            visitor.visit_expression_mut(target);
+            for (expression, _) in chain {
+                visitor.visit_expression_mut(expression);
+            }
+            visitor.visit_expression_mut(value);
+        }
+        StatementKind::If {
+            branches,
+            else_body,
+        } => {
+            for branch in branches {
+                visitor.visit_expression_mut(&mut branch.condition);
+                walk_statements_mut(visitor, &mut branch.body);
+            }
+            if let Some(body) = else_body {
+                walk_statements_mut(visitor, body);
+            }
+        }
+        StatementKind::Case {
+            selector,
+            arms,
+            else_body,
+        } => {
+            visitor.visit_expression_mut(selector);
+            for arm in arms {
+                for label in &mut arm.labels {
+                    visitor.visit_case_label_mut(label);
+                }
+                walk_statements_mut(visitor, &mut arm.body);
+            }
+            if let Some(body) = else_body {
+                walk_statements_mut(visitor, body);
+            }
+        }
+        StatementKind::For {
+            var,
+            from,
+            to,
+            by,
+            body,
+        } => {
+            visitor.visit_identifier_mut(var);
+            visitor.visit_expression_mut(from);
+            visitor.visit_expression_mut(to);
+            if let Some(by) = by {
+                visitor.visit_expression_mut(by);
+            }
+            walk_statements_mut(visitor, body);
+        }
+        StatementKind::While { condition, body } => {
+            visitor.visit_expression_mut(condition);
+            walk_statements_mut(visitor, body);
+        }
+        StatementKind::Repeat { body, until } => {
+            walk_statements_mut(visitor, body);
+            visitor.visit_expression_mut(until);
+        }
+        StatementKind::Jump(label) | StatementKind::Label(label) => {
+            visitor.visit_identifier_mut(label)
+        }
+        StatementKind::Call(expression) => visitor.visit_expression_mut(expression),
+        StatementKind::Return
+        | StatementKind::Exit
+        | StatementKind::Continue
+        | StatementKind::Empty
+        | StatementKind::Unrecognised => (),
+    }
+    for ether in &mut statement.trailing {
+        visitor.visit_ether_mut(ether);
+    }
+}
+
+fn walk_statements_mut<V: VisitMut + ?Sized>(visitor: &mut V, statements: &mut [Statement]) {
+    for statement in statements {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_case_label_mut<V: VisitMut + ?Sized>(visitor: &mut V, case_label: &mut CaseLabel) {
+    match case_label {
+        CaseLabel::Single(value) => visitor.visit_expression_mut(value),
+        CaseLabel::Range(lower, upper) => {
+            visitor.visit_expression_mut(lower);
+            visitor.visit_expression_mut(upper);
+        }
+    }
+}
+
+pub fn walk_expression_mut<V: VisitMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    visitor.visit_expression_inner_mut(&mut expression.0);
+}
+
+pub fn walk_expression_inner_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    expression: &mut ExpressionInner,
+) {
+    match expression {
+        ExpressionInner::BinaryOperator(inner) => visitor.visit_binary_operator_mut(inner),
+        ExpressionInner::Bracket(ethers0, inner, ethers1) => {
+            for ether in ethers0 {
+                visitor.visit_ether_mut(ether);
+            }
+            visitor.visit_expression_inner_mut(inner);
+            for ether in ethers1 {
+                visitor.visit_ether_mut(ether);
+            }
+        }
+        ExpressionInner::Dereference(inner)
+        | ExpressionInner::Negative(inner)
+        | ExpressionInner::Positive(inner) => visitor.visit_expression_inner_mut(inner),
+        ExpressionInner::Field(inner, member) => {
+            visitor.visit_expression_inner_mut(inner);
+            visitor.visit_member_mut(member);
+        }
+        ExpressionInner::FunctionCall(inner) => visitor.visit_function_call_mut(inner),
+        ExpressionInner::Index(inner, indices) => {
+            visitor.visit_expression_inner_mut(inner);
+            for index in indices {
+                visitor.visit_expression_inner_mut(index);
+            }
+        }
+        ExpressionInner::Method(inner, method) => {
+            visitor.visit_expression_inner_mut(inner);
+            visitor.visit_function_call_mut(method);
+        }
+        ExpressionInner::Not(_, ethers, inner) => {
+            for ether in ethers {
+                visitor.visit_ether_mut(ether);
+            }
+            visitor.visit_expression_inner_mut(inner);
+        }
+        ExpressionInner::Value(inner) => visitor.visit_value_mut(inner),
+    }
+}
+
+pub fn walk_binary_operator_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    binary_operator: &mut BinaryOperator,
+) {
+    visitor.visit_expression_inner_mut(&mut binary_operator.left);
+    for ether in &mut binary_operator.ethers0 {
+        visitor.visit_ether_mut(ether);
+    }
+    visitor.visit_operator_mut(&mut binary_operator.operator);
+    for ether in &mut binary_operator.ethers1 {
+        visitor.visit_ether_mut(ether);
+    }
+    visitor.visit_expression_inner_mut(&mut binary_operator.right);
+}
+
+pub fn walk_function_call_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    function_call: &mut FunctionCall,
+) {
+    visitor.visit_expression_inner_mut(&mut function_call.identifier);
+    for ether in &mut function_call.ethers {
+        visitor.visit_ether_mut(ether);
+    }
+    for (argument, ethers) in &mut function_call.arguments {
+        visitor.visit_argument_mut(argument);
+        for ether in ethers {
+            visitor.visit_ether_mut(ether);
+        }
+    }
+}
+
+pub fn walk_argument_list_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    argument_list: &mut ArgumentList,
+) {
+    for ether in &mut argument_list.ethers {
+        visitor.visit_ether_mut(ether);
+    }
+    for (argument, ethers) in &mut argument_list.arguments {
+        visitor.visit_argument_mut(argument);
+        for ether in ethers {
+            visitor.visit_ether_mut(ether);
+        }
+    }
+}
+
+pub fn walk_argument_mut<V: VisitMut + ?Sized>(visitor: &mut V, argument: &mut Argument) {
+    let expression = match argument {
+        Argument::Unnamed(expression) => expression,
+        Argument::InputOrInout(identifier, expression)
+        | Argument::Output(identifier, expression) => {
+            visitor.visit_identifier_mut(identifier);
+            expression
+        }
+    };
+    if let Some(expression) = expression {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+pub fn walk_value_mut<V: VisitMut + ?Sized>(visitor: &mut V, value: &mut Value) {
+    match &mut value.0 {
+        ValueInner::Array(inner) => visitor.visit_array_mut(inner),
+        ValueInner::Struct(inner) => visitor.visit_struct_mut(inner),
+        ValueInner::Literal(literal) => visitor.visit_literal_mut(literal),
+        ValueInner::String(literal) => visitor.visit_string_literal_mut(literal),
+        ValueInner::Flat(_) => (),
+    }
+}
+
+pub fn walk_array_mut<V: VisitMut + ?Sized>(visitor: &mut V, array: &mut Array) {
+    for ether in &mut array.0 {
+        visitor.visit_ether_mut(ether);
+    }
+    for (element, ethers) in &mut array.1 {
+        match element {
+            ArrayElement::Single(expression) => visitor.visit_expression_mut(expression),
+            ArrayElement::Repeated(count, value) => {
+                visitor.visit_literal_mut(count);
+                if let Some(value) = value {
+                    visitor.visit_expression_mut(value);
+                }
+            }
+        }
+        for ether in ethers {
+            visitor.visit_ether_mut(ether);
+        }
+    }
+}
+
+pub fn walk_struct_mut<V: VisitMut + ?Sized>(visitor: &mut V, s: &mut Struct) {
+    for (identifier, expression, ethers) in &mut s.0 {
+        visitor.visit_identifier_mut(identifier);
+        visitor.visit_expression_mut(expression);
+        for ether in ethers {
+            visitor.visit_ether_mut(ether);
+        }
+    }
+}
+
+pub fn walk_data_type_mut<V: VisitMut + ?Sized>(visitor: &mut V, data_type: &mut DataType) {
+    match data_type {
+        DataType::Array(ranges, inner, _) => {
+            for range in ranges {
+                visitor.visit_array_range_mut(range);
+            }
+            visitor.visit_data_type_mut(inner);
+        }
+        DataType::ReferenceTo(inner, _) | DataType::PointerTo(inner, _) => {
+            visitor.visit_data_type_mut(inner)
+        }
+        DataType::Subrange(inner, lower, upper) => {
+            visitor.visit_data_type_mut(inner);
+            visitor.visit_expression_mut(lower);
+            visitor.visit_expression_mut(upper);
+        }
+        DataType::ImplicitEnum(members) => {
+            for member in members {
+                visitor.visit_identifier_mut(member);
+            }
+        }
+        DataType::String(length, _) | DataType::WString(length, _) => {
+            if let Some(length) = length {
+                visitor.visit_expression_mut(length);
+            }
+        }
+        DataType::Elementary(..) | DataType::Flat(_) => (),
+    }
+}
+
+pub fn walk_array_range_mut<V: VisitMut + ?Sized>(visitor: &mut V, array_range: &mut ArrayRange) {
+    if let ArrayRange::LowerUpper(lower, upper) = array_range {
+        visitor.visit_expression_mut(lower);
+        visitor.visit_expression_mut(upper);
+    }
+}
+
+#[cfg(test)]
+#[path = "./test_visit.rs"]
+mod test_visit;